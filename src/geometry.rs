@@ -0,0 +1,183 @@
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+pub type Matrix3 = [[i32; 3]; 3];
+
+// A rotation by multiples of 90 degrees around the axes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation(Matrix3);
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    fn coords(&self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn manhattan(&self, other: &Point3) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    // Invariant under rotations and translations, handy to fingerprint point clouds
+    pub fn squared_distance(&self, other: &Point3) -> i64 {
+        let d = *self - *other;
+        d.coords().iter().map(|&c| c as i64 * c as i64).sum()
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl FromStr for Point3 {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut coords = s
+            .trim()
+            .split(',')
+            .map(|c| c.trim().parse().map_err(|_| ()));
+
+        match (coords.next(), coords.next(), coords.next(), coords.next()) {
+            (Some(x), Some(y), Some(z), None) => Ok(Point3::new(x?, y?, z?)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    // The 24 orientations of a cube: signed permutation matrices with determinant 1
+    pub fn all() -> Vec<Rotation> {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        PERMUTATIONS
+            .iter()
+            .flat_map(|perm| {
+                (0..8).map(move |signs: usize| {
+                    let mut matrix = [[0; 3]; 3];
+                    for (row, &col) in perm.iter().enumerate() {
+                        matrix[row][col] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                    }
+                    Rotation(matrix)
+                })
+            })
+            .filter(|rotation| rotation.determinant() == 1)
+            .collect()
+    }
+
+    fn determinant(&self) -> i32 {
+        let m = &self.0;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn apply(&self, point: Point3) -> Point3 {
+        let c = point.coords();
+        let [x, y, z] = self
+            .0
+            .map(|row| row.iter().zip(c).map(|(a, b)| a * b).sum());
+
+        Point3::new(x, y, z)
+    }
+
+    // The rotation applying `other` first, then `self`
+    pub fn compose(&self, other: &Rotation) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.0[i][k] * other.0[k][j]).sum();
+            }
+        }
+
+        Rotation(matrix)
+    }
+
+    pub fn inverse(&self) -> Rotation {
+        // Rotation matrices are orthogonal, so the inverse is the transpose
+        let mut matrix = [[0; 3]; 3];
+        for (i, row) in self.0.iter().enumerate() {
+            for (j, &cell) in row.iter().enumerate() {
+                matrix[j][i] = cell;
+            }
+        }
+
+        Rotation(matrix)
+    }
+}
+
+#[test]
+fn test_rotations() {
+    let rotations = Rotation::all();
+    assert_eq!(rotations.len(), 24);
+    assert!(rotations.contains(&Rotation::IDENTITY));
+
+    let p = Point3::new(1, 2, 3);
+    for a in &rotations {
+        assert_eq!(a.inverse().apply(a.apply(p)), p);
+        for b in &rotations {
+            assert!(rotations.contains(&a.compose(b)));
+            assert_eq!(a.compose(b).apply(p), a.apply(b.apply(p)));
+        }
+    }
+}
+
+#[test]
+fn test_point() {
+    let a: Point3 = "1,-2,3".parse().unwrap();
+    let b = Point3::new(-1, 0, 3);
+
+    assert_eq!(a - b, Point3::new(2, -2, 0));
+    assert_eq!(a.manhattan(&b), 4);
+    assert_eq!(a.squared_distance(&b), 8);
+    assert_eq!(a.to_string(), "1,-2,3");
+    assert_eq!("1,2".parse::<Point3>(), Err(()));
+}
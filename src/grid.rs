@@ -0,0 +1,172 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Coordinate = (usize, usize);
+
+// North, east, south, west
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+pub const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // One row per line, one cell per char
+    pub fn parse<F>(input: &str, f: F) -> Self
+    where
+        F: Fn(char) -> T,
+    {
+        let width = input.lines().next().map_or(0, |l| l.chars().count());
+        let cells: Vec<T> = input.lines().flat_map(|l| l.chars().map(&f)).collect();
+
+        assert!(
+            width == 0 || cells.len().is_multiple_of(width),
+            "Grid rows must have the same length"
+        );
+
+        Self {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Coordinate) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): Coordinate) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    // Moves by (dx, dy), returning None when leaving the grid
+    pub fn offset(&self, (x, y): Coordinate, (dx, dy): (isize, isize)) -> Option<Coordinate> {
+        let nx = x.checked_add_signed(dx)?;
+        let ny = y.checked_add_signed(dy)?;
+
+        (nx < self.width && ny < self.height).then_some((nx, ny))
+    }
+
+    // Moves by (dx, dy), wrapping around the edges as on a torus
+    pub fn wrapping_offset(&self, (x, y): Coordinate, (dx, dy): (isize, isize)) -> Coordinate {
+        (
+            (x as isize + dx).rem_euclid(self.width as isize) as usize,
+            (y as isize + dy).rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    pub fn neighbours<'a>(
+        &'a self,
+        coord: Coordinate,
+        deltas: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Coordinate> + 'a {
+        deltas
+            .iter()
+            .filter_map(move |&delta| self.offset(coord, delta))
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.coordinates().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coordinate) -> &T {
+        self.get(coord).expect("Coordinate out of the grid")
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, coord: Coordinate) -> &mut T {
+        self.get_mut(coord).expect("Coordinate out of the grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_parse() {
+    let grid = Grid::parse("123\n456", |c| c.to_digit(10).unwrap());
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 1)], 6);
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.to_string(), "123\n456\n");
+}
+
+#[test]
+fn test_neighbours() {
+    let grid = Grid::new(3, 3, 0);
+
+    assert_eq!(
+        grid.neighbours((0, 0), &ORTHOGONAL).collect::<Vec<_>>(),
+        vec![(1, 0), (0, 1)]
+    );
+    assert_eq!(grid.neighbours((1, 1), &ADJACENT).count(), 8);
+    assert_eq!(grid.wrapping_offset((0, 2), (-1, 1)), (2, 0));
+}
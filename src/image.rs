@@ -0,0 +1,134 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+pub trait Pixel: Copy + Default {
+    // Netpbm magic number of the binary format storing this kind of pixel
+    const MAGIC: &'static str;
+
+    fn bytes(&self) -> &[u8];
+}
+
+impl Pixel for u8 {
+    const MAGIC: &'static str = "P5";
+
+    fn bytes(&self) -> &[u8] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl Pixel for Rgb {
+    const MAGIC: &'static str = "P6";
+
+    fn bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image<P: Pixel> {
+    width: usize,
+    height: usize,
+    pixels: Vec<P>,
+}
+
+impl<P: Pixel> Image<P> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![P::default(); width * height],
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, pixel: P) {
+        self.pixels[y * self.width + x] = pixel;
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> P {
+        self.pixels[y * self.width + x]
+    }
+
+    #[cfg(test)]
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    // Every pixel becomes a `factor`x`factor` square, small grids are unreadable otherwise
+    pub fn scaled(&self, factor: usize) -> Self {
+        let mut scaled = Self::new(self.width * factor, self.height * factor);
+
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.set(x, y, self.get(x / factor, y / factor));
+            }
+        }
+
+        scaled
+    }
+
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "{}\n{} {}\n255\n", P::MAGIC, self.width, self.height)?;
+
+        for pixel in &self.pixels {
+            out.write_all(pixel.bytes())?;
+        }
+
+        out.flush()
+    }
+
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> io::Result<()> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+}
+
+// Distinct, bright colours for an arbitrary number of regions
+pub fn palette(i: usize) -> Rgb {
+    // Walking the hue circle by the golden angle never gives two close neighbours
+    let hue = (i as f64 * 137.507_764) % 360.0;
+    let x = 1.0 - ((hue / 60.0) % 2.0 - 1.0).abs();
+
+    let (r, g, b) = match (hue / 60.0) as usize {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+
+    [(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8]
+}
+
+#[test]
+fn test_pgm() {
+    let mut image: Image<u8> = Image::new(2, 1);
+    image.set(1, 0, 255);
+
+    let mut out = Vec::new();
+    image.write(&mut out).unwrap();
+
+    assert_eq!(out, b"P5\n2 1\n255\n\x00\xff");
+}
+
+#[test]
+fn test_ppm_scaled() {
+    let mut image: Image<Rgb> = Image::new(1, 1);
+    image.set(0, 0, [1, 2, 3]);
+
+    let mut out = Vec::new();
+    image.scaled(2).write(&mut out).unwrap();
+
+    assert_eq!(
+        out,
+        b"P6\n2 2\n255\n\x01\x02\x03\x01\x02\x03\x01\x02\x03\x01\x02\x03"
+    );
+}
+
+#[test]
+fn test_palette() {
+    assert_eq!(palette(0), [255, 0, 0]);
+    assert_ne!(palette(1), palette(2));
+}
@@ -0,0 +1,11 @@
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod parsing;
+#[cfg(test)]
+mod random;
+pub mod registry;
+pub mod render;
+pub mod repl;
+pub mod search;
+pub mod y2021;
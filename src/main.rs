@@ -1,18 +1,220 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-mod day17;
+use aoc::registry::{self, YEARS};
+use aoc::render::{Output, Renderer};
+
+const DEFAULT_FPS: u32 = 10;
+
+const USAGE: &str = "Usage:
+  aoc                                                  run the latest solved day
+  aoc run <year> [day]                                 run a day, or every day of a year
+  aoc stream <year> <day> <part> [path]                solve a part reading the input from a file or stdin
+  aoc repl <year> <day>                                explore a simulation step by step
+  aoc visualize <year> <day> [--fps <n> | --out <dir>] animate a simulation
+  aoc image <year> <day> <path> [param]                export a picture of a puzzle
+  aoc export <year> <day> <part> [path]                export the data of a part to a file or stdout";
+
+fn read_input(year: u16, day: usize) -> String {
+    let path = registry::input_path(year, day);
+    fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Cannot read {}: {}", path.display(), err))
+}
+
+fn run(year: u16, day: usize) {
+    let (p1, p2) = match registry::parts(year, day) {
+        Some(parts) => parts,
+        None => {
+            eprintln!("No solution for {} day {}", year, day);
+            return;
+        }
+    };
+    let input = read_input(year, day);
 
-fn main() {
     let now = Instant::now();
-    let p1_ans = day17::p1(include_str!("../inputs/day17.txt"));
+    let p1_ans = p1(&input);
     let p1_took = now.elapsed();
 
     let now = Instant::now();
-    let p2_ans = day17::p2(include_str!("../inputs/day17.txt"));
+    let p2_ans = p2(&input);
     let p2_took = now.elapsed();
 
-    println!("P1 Solution: {:?}", p1_ans);
+    println!("P1 Solution: {}", p1_ans);
     println!("P1 Took: {:?}", p1_took);
-    println!("P2 Solution: {:?}", p2_ans);
+    println!("P2 Solution: {}", p2_ans);
     println!("P2 Took: {:?}", p2_took);
 }
+
+fn run_year(year: u16) {
+    if registry::days(year).next().is_none() {
+        eprintln!("No solution for {}", year);
+        return;
+    }
+
+    for day in registry::days(year) {
+        println!("== {} day {:02} ==", year, day);
+        if registry::input_path(year, day).exists() {
+            run(year, day);
+        } else {
+            println!("No input, skipped");
+        }
+    }
+}
+
+fn stream(year: u16, day: usize, part: usize, path: Option<&Path>) {
+    let (p1, p2) = match registry::stream(year, day) {
+        Some(parts) => parts,
+        None => {
+            eprintln!("No streaming solution for {} day {}", year, day);
+            return;
+        }
+    };
+    let solve = match part {
+        1 => p1,
+        2 => p2,
+        _ => {
+            eprintln!("{}", USAGE);
+            return;
+        }
+    };
+
+    let now = Instant::now();
+    let ans = match path {
+        Some(path) => {
+            let file = File::open(path)
+                .unwrap_or_else(|err| panic!("Cannot read {}: {}", path.display(), err));
+            solve(&mut BufReader::new(file))
+        }
+        None => solve(&mut io::stdin().lock()),
+    };
+
+    println!("P{} Solution: {}", part, ans);
+    println!("P{} Took: {:?}", part, now.elapsed());
+}
+
+fn repl(year: u16, day: usize) {
+    let parts = match registry::parts(year, day) {
+        Some(parts) => parts,
+        None => {
+            eprintln!("No solution for {} day {}", year, day);
+            return;
+        }
+    };
+
+    match registry::repl(year, day) {
+        Some(repl) => repl(
+            &format!("{}/day{:02}", year, day),
+            &registry::input_path(year, day),
+            parts,
+        ),
+        None => eprintln!("No REPL available for {} day {}", year, day),
+    }
+}
+
+fn visualize(year: u16, day: usize, output: Output) {
+    let visualize = match registry::visualizer(year, day) {
+        Some(visualize) => visualize,
+        None => {
+            eprintln!("No visualization available for {} day {}", year, day);
+            return;
+        }
+    };
+
+    let input = read_input(year, day);
+    let mut renderer = Renderer::new(output).expect("Cannot create renderer");
+
+    visualize(&input, &mut renderer).expect("Cannot render frame");
+}
+
+fn image(year: u16, day: usize, path: &Path, param: Option<usize>) {
+    match registry::exporter(year, day) {
+        Some(export) => export(&read_input(year, day), path, param).expect("Cannot write image"),
+        None => eprintln!("No image available for {} day {}", year, day),
+    }
+}
+
+fn export(year: u16, day: usize, part: usize, path: Option<&Path>) {
+    let (p1, p2) = match registry::data(year, day) {
+        Some(parts) => parts,
+        None => {
+            eprintln!("No data available for {} day {}", year, day);
+            return;
+        }
+    };
+    let data = match part {
+        1 => p1,
+        2 => p2,
+        _ => {
+            eprintln!("{}", USAGE);
+            return;
+        }
+    };
+
+    match (data(&read_input(year, day)), path) {
+        (Ok(data), Some(path)) => fs::write(path, data)
+            .unwrap_or_else(|err| panic!("Cannot write {}: {}", path.display(), err)),
+        (Ok(data), None) => print!("{}", data),
+        (Err(err), _) => eprintln!("Cannot export {} day {}: {}", year, day, err),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let year = |year: &str| year.parse::<u16>().expect("Cannot parse year");
+    let day = |day: &str| day.parse::<usize>().expect("Cannot parse day");
+
+    match args[..] {
+        [] => {
+            let latest_year = *YEARS.last().unwrap();
+            let latest_day = registry::days(latest_year)
+                .filter(|&day| registry::input_path(latest_year, day).exists())
+                .last()
+                .expect("No input available");
+            run(latest_year, latest_day)
+        }
+        ["run", y] => run_year(year(y)),
+        ["run", y, d] => run(year(y), day(d)),
+        ["stream", y, d, part] => stream(year(y), day(d), part.parse().unwrap_or(0), None),
+        ["stream", y, d, part, path] => stream(
+            year(y),
+            day(d),
+            part.parse().unwrap_or(0),
+            Some(Path::new(path)),
+        ),
+        ["repl", y, d] => repl(year(y), day(d)),
+        ["visualize", y, d, ref options @ ..] => {
+            let output = match options {
+                [] => Output::Terminal { fps: DEFAULT_FPS },
+                ["--fps", fps] => Output::Terminal {
+                    fps: fps.parse().expect("Cannot parse fps"),
+                },
+                ["--out", dir] => Output::Directory(PathBuf::from(dir)),
+                _ => {
+                    eprintln!("{}", USAGE);
+                    return;
+                }
+            };
+            visualize(year(y), day(d), output)
+        }
+        ["image", y, d, path] => image(year(y), day(d), Path::new(path), None),
+        ["image", y, d, path, param] => image(
+            year(y),
+            day(d),
+            Path::new(path),
+            Some(param.parse().expect("Cannot parse image parameter")),
+        ),
+        ["export", y, d, part] => export(year(y), day(d), part.parse().unwrap_or(0), None),
+        ["export", y, d, part, path] => export(
+            year(y),
+            day(d),
+            part.parse().unwrap_or(0),
+            Some(Path::new(path)),
+        ),
+        _ => eprintln!("{}", USAGE),
+    }
+}
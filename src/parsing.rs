@@ -0,0 +1,59 @@
+use std::fmt::Debug;
+use std::io::BufRead;
+use std::str::FromStr;
+
+// Parses every non empty line, pulling them from a reader one at a time, so the input never has to fit in memory
+pub fn read_lines<T, R>(reader: R) -> impl Iterator<Item = T>
+where
+    T: FromStr,
+    T::Err: Debug,
+    R: BufRead,
+{
+    reader
+        .lines()
+        .map(|l| l.expect("Cannot read line"))
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.trim().parse().unwrap())
+}
+
+pub fn separated<T>(input: &str, sep: char) -> impl Iterator<Item = T> + '_
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    input.trim().split(sep).map(|n| n.trim().parse().unwrap())
+}
+
+// Reads the bytes of an iterator, to feed readers with inputs generated on the fly
+#[cfg(test)]
+pub struct Generated<I>(pub I);
+
+#[cfg(test)]
+impl<I: Iterator<Item = u8>> std::io::Read for Generated<I> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(buf
+            .iter_mut()
+            .zip(&mut self.0)
+            .map(|(b, byte)| *b = byte)
+            .count())
+    }
+}
+
+#[test]
+fn test_read_lines() {
+    // A tiny buffer forces lines to be read across several refills
+    let reader = std::io::BufReader::with_capacity(2, "10\n 20\n\n300\n".as_bytes());
+
+    assert_eq!(
+        read_lines::<usize, _>(reader).collect::<Vec<_>>(),
+        [10, 20, 300]
+    );
+}
+
+#[test]
+fn test_separated() {
+    assert_eq!(
+        separated::<isize>("3,-4, 5\n", ',').collect::<Vec<_>>(),
+        [3, -4, 5]
+    );
+}
@@ -0,0 +1,22 @@
+// Deterministic pseudo-random numbers, so generated test inputs are the same on every run
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    // The high bits, the low ones of a linear congruential generator being poorly distributed
+    pub fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+
+    // In 0..n
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
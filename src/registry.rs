@@ -0,0 +1,98 @@
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+use crate::render::Renderer;
+use crate::y2021;
+
+pub type Part = fn(&str) -> String;
+// Reads the input as it goes, for inputs too big to be loaded at once
+pub type Stream = fn(&mut dyn BufRead) -> String;
+pub type Repl = fn(&str, &Path, (Part, Part));
+pub type Visualizer = fn(&str, &mut Renderer) -> io::Result<()>;
+// The optional parameter is day specific, e.g. the number of steps to simulate
+pub type Exporter = fn(&str, &Path, Option<usize>) -> io::Result<()>;
+// Turns the input into text data, e.g. CSV, or explains why it cannot
+pub type Data = fn(&str) -> Result<String, String>;
+
+pub const YEARS: [u16; 1] = [2021];
+
+macro_rules! solution {
+    ($day:ident) => {
+        (
+            |input: &str| format!("{:?}", $day::p1(input)),
+            |input: &str| format!("{:?}", $day::p2(input)),
+        )
+    };
+}
+pub(crate) use solution;
+
+macro_rules! streamed {
+    ($day:ident) => {
+        (
+            |reader: &mut dyn std::io::BufRead| format!("{:?}", $day::stream_p1(reader)),
+            |reader: &mut dyn std::io::BufRead| format!("{:?}", $day::stream_p2(reader)),
+        )
+    };
+}
+pub(crate) use streamed;
+
+pub fn input_path(year: u16, day: usize) -> PathBuf {
+    PathBuf::from(format!("inputs/{}/day{:02}.txt", year, day))
+}
+
+pub fn parts(year: u16, day: usize) -> Option<(Part, Part)> {
+    match year {
+        2021 => y2021::parts(day),
+        _ => None,
+    }
+}
+
+pub fn days(year: u16) -> impl Iterator<Item = usize> {
+    (1..=25).filter(move |&day| parts(year, day).is_some())
+}
+
+pub fn stream(year: u16, day: usize) -> Option<(Stream, Stream)> {
+    match year {
+        2021 => y2021::stream(day),
+        _ => None,
+    }
+}
+
+pub fn repl(year: u16, day: usize) -> Option<Repl> {
+    match year {
+        2021 => y2021::repl(day),
+        _ => None,
+    }
+}
+
+pub fn visualizer(year: u16, day: usize) -> Option<Visualizer> {
+    match year {
+        2021 => y2021::visualizer(day),
+        _ => None,
+    }
+}
+
+pub fn exporter(year: u16, day: usize) -> Option<Exporter> {
+    match year {
+        2021 => y2021::exporter(day),
+        _ => None,
+    }
+}
+
+pub fn data(year: u16, day: usize) -> Option<(Data, Data)> {
+    match year {
+        2021 => y2021::data(day),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_registry() {
+    assert_eq!(days(2021).next(), Some(1));
+    assert!(parts(2020, 1).is_none());
+    assert_eq!(input_path(2021, 5), PathBuf::from("inputs/2021/day05.txt"));
+
+    // Data and pictures are exported separately
+    assert!(data(2021, 2).is_some() && exporter(2021, 2).is_none());
+    assert!(data(2021, 5).is_none() && exporter(2021, 5).is_some());
+}
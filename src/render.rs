@@ -0,0 +1,181 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Cyan,
+    Gray,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: char,
+    color: Option<Color>,
+}
+
+const BLANK: Cell = Cell {
+    ch: ' ',
+    color: None,
+};
+
+#[derive(Debug)]
+pub struct Frame {
+    title: String,
+    width: usize,
+    cells: Vec<Cell>,
+}
+
+#[derive(Debug)]
+pub enum Output {
+    Terminal { fps: u32 },
+    Directory(PathBuf),
+}
+
+#[derive(Debug)]
+pub struct Renderer {
+    output: Output,
+    frames: usize,
+}
+
+impl Color {
+    fn ansi_code(&self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Cyan => 36,
+            Color::Gray => 90,
+        }
+    }
+}
+
+impl Frame {
+    pub fn new(title: impl Into<String>, width: usize, height: usize) -> Self {
+        Self {
+            title: title.into(),
+            width,
+            cells: vec![BLANK; width * height],
+        }
+    }
+
+    // Out of bounds coordinates are silently ignored
+    pub fn set(&mut self, x: usize, y: usize, ch: char, color: Option<Color>) {
+        if x < self.width && y < self.cells.len() / self.width {
+            self.cells[y * self.width + x] = Cell { ch, color };
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    fn plain(&self) -> String {
+        let mut out = format!("{}\n", self.title);
+
+        for row in self.rows() {
+            out.extend(row.iter().map(|cell| cell.ch));
+            out.push('\n');
+        }
+
+        out
+    }
+
+    fn ansi(&self) -> String {
+        let mut out = format!("\x1b[1m{}\x1b[0m\n", self.title);
+
+        for row in self.rows() {
+            let mut current = None;
+
+            for cell in row {
+                if cell.color != current {
+                    match cell.color {
+                        Some(color) => write!(out, "\x1b[{}m", color.ansi_code()).unwrap(),
+                        None => out.push_str("\x1b[0m"),
+                    }
+                    current = cell.color;
+                }
+                out.push(cell.ch);
+            }
+
+            if current.is_some() {
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+impl Renderer {
+    pub fn new(output: Output) -> io::Result<Self> {
+        if let Output::Directory(ref dir) = output {
+            fs::create_dir_all(dir)?;
+        }
+
+        Ok(Self { output, frames: 0 })
+    }
+
+    pub fn draw(&mut self, frame: &Frame) -> io::Result<()> {
+        match self.output {
+            Output::Terminal { fps } => {
+                let mut stdout = io::stdout().lock();
+                // Clear the screen and move the cursor to the top-left corner
+                write!(stdout, "\x1b[2J\x1b[H{}", frame.ansi())?;
+                stdout.flush()?;
+                thread::sleep(Duration::from_secs_f64(1.0 / fps.max(1) as f64));
+            }
+            Output::Directory(ref dir) => {
+                fs::write(
+                    dir.join(format!("frame_{:05}.txt", self.frames)),
+                    frame.plain(),
+                )?;
+            }
+        }
+
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+// Runs a visualization into a scratch directory and returns the plain text of its frames
+#[cfg(test)]
+pub fn record<F>(name: &str, visualize: F) -> Vec<String>
+where
+    F: FnOnce(&mut Renderer) -> io::Result<()>,
+{
+    let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let mut renderer = Renderer::new(Output::Directory(dir.clone())).unwrap();
+    visualize(&mut renderer).unwrap();
+
+    let frames = (0..renderer.frames)
+        .map(|i| fs::read_to_string(dir.join(format!("frame_{:05}.txt", i))).unwrap())
+        .collect();
+    fs::remove_dir_all(&dir).unwrap();
+
+    frames
+}
+
+#[test]
+fn test_frame() {
+    let mut frame = Frame::new("Test", 3, 2);
+    frame.set(0, 0, '#', Some(Color::Red));
+    frame.set(1, 0, '#', Some(Color::Red));
+    frame.set(2, 1, '.', None);
+    frame.set(3, 1, 'x', None);
+
+    assert_eq!(frame.plain(), "Test\n## \n  .\n");
+    assert_eq!(
+        frame.ansi(),
+        "\x1b[1mTest\x1b[0m\n\x1b[31m##\x1b[0m \n  .\n"
+    );
+}
@@ -0,0 +1,97 @@
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::registry::Part;
+
+pub trait Simulation: Display + Debug + Sized {
+    // Fails with a message for the user on a malformed input
+    fn load(input: &str) -> Result<Self, String>;
+
+    // Returns false when the simulation cannot advance anymore
+    fn step(&mut self) -> bool;
+
+    fn steps(&self) -> usize;
+}
+
+const HELP: &str = "Commands:
+  load [path]   load the input (defaults to the day's input) and reset the state
+  reset         reset the state to the loaded input
+  step [n]      advance the simulation by n steps (default 1)
+  print         print the state
+  inspect       dump the state with all its fields
+  p1 | p2       evaluate a part on the loaded input
+  help          show this message
+  quit          exit the repl";
+
+pub fn run<S: Simulation>(name: &str, default_path: &Path, (p1, p2): (Part, Part)) {
+    let mut input = match fs::read_to_string(default_path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Cannot read {}: {}", default_path.display(), err);
+            return;
+        }
+    };
+    let mut state = match S::load(&input) {
+        Ok(state) => state,
+        Err(err) => {
+            eprintln!("Cannot load {}: {}", default_path.display(), err);
+            return;
+        }
+    };
+
+    println!(
+        "{} loaded from {}, type `help` for commands",
+        name,
+        default_path.display()
+    );
+
+    let stdin = io::stdin();
+    loop {
+        print!("{}[{}]> ", name, state.steps());
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (None, _) => continue,
+            (Some("load"), path) => {
+                let path = path.map_or(default_path, Path::new);
+                // A malformed input leaves the previous one and its state untouched
+                match fs::read_to_string(path) {
+                    Ok(new_input) => match S::load(&new_input) {
+                        Ok(new_state) => {
+                            input = new_input;
+                            state = new_state;
+                            println!("Loaded {}", path.display());
+                        }
+                        Err(err) => println!("Cannot load {}: {}", path.display(), err),
+                    },
+                    Err(err) => println!("Cannot read {}: {}", path.display(), err),
+                }
+            }
+            (Some("reset"), None) => state = S::load(&input).expect("Input loaded before"),
+            (Some("step"), n) => match n.map_or(Ok(1), str::parse::<usize>) {
+                Ok(n) => {
+                    let done = (0..n).take_while(|_| state.step()).count();
+                    if done < n {
+                        println!("Simulation ended after {} steps", done);
+                    }
+                }
+                Err(err) => println!("Invalid number of steps: {}", err),
+            },
+            (Some("print"), None) => println!("{}", state),
+            (Some("inspect"), None) => println!("{:#?}", state),
+            (Some("p1"), None) => println!("{}", p1(&input)),
+            (Some("p2"), None) => println!("{}", p2(&input)),
+            (Some("help"), None) => println!("{}", HELP),
+            (Some("quit" | "exit"), None) => break,
+            (Some(cmd), _) => println!("Unknown command `{}`, type `help` for commands", cmd),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+// The total cost and the states from the start to the goal, both included
+pub type Path<S> = (usize, Vec<S>);
+
+pub fn dijkstra<S, I>(
+    start: S,
+    is_goal: impl Fn(&S) -> bool,
+    neighbours: impl FnMut(&S) -> I,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    search(start, is_goal, neighbours, |_| 0, |_, _| ())
+}
+
+pub fn astar<S, I>(
+    start: S,
+    is_goal: impl Fn(&S) -> bool,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> usize,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    search(start, is_goal, neighbours, heuristic, |_, _| ())
+}
+
+// The heuristic must never overestimate the remaining cost, a null one gives Dijkstra.
+// `visit` is called on every settled state along with the best costs known so far
+pub fn search<S, I>(
+    start: S,
+    is_goal: impl Fn(&S) -> bool,
+    mut neighbours: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> usize,
+    mut visit: impl FnMut(&S, &HashMap<S, usize>),
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut came_from: HashMap<S, S> = HashMap::new();
+
+    // States don't have to be ordered, the heap refers to them by their index in `queued`
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut queued = vec![start];
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = queued[index].clone();
+
+        // A cheaper way to this state has already been settled
+        if cost > dist[&state] {
+            continue;
+        }
+
+        visit(&state, &dist);
+
+        if is_goal(&state) {
+            let mut path = vec![state];
+            while let Some(prev) = came_from.get(path.last().unwrap()) {
+                path.push(prev.clone());
+            }
+            path.reverse();
+
+            return Some((cost, path));
+        }
+
+        for (next, step) in neighbours(&state) {
+            let tcost = cost + step;
+
+            if dist.get(&next).is_none_or(|&d| tcost < d) {
+                dist.insert(next.clone(), tcost);
+                came_from.insert(next.clone(), state.clone());
+                heap.push(Reverse((tcost + heuristic(&next), tcost, queued.len())));
+                queued.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_search() {
+    // Reach 100 from 1 by adding 1 (cost 1) or doubling (cost 3)
+    let neighbours = |&n: &usize| {
+        [(n + 1, 1), (n * 2, 3)]
+            .into_iter()
+            .filter(|&(m, _)| m <= 100)
+    };
+
+    let (cost, path) = dijkstra(1, |&n| n == 100, neighbours).unwrap();
+    assert_eq!(cost, 18);
+    assert_eq!(path.first(), Some(&1));
+    assert_eq!(path.last(), Some(&100));
+
+    let heuristic = |&n: &usize| if n == 100 { 0 } else { 1 };
+    assert_eq!(
+        astar(1, |&n| n == 100, neighbours, heuristic).unwrap().0,
+        18
+    );
+
+    assert_eq!(dijkstra(1, |&n| n == 0, neighbours), None);
+}
@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::io::BufRead;
+
+use crate::parsing;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub depths: usize,
+    pub increases: usize,
+    pub decreases: usize,
+    // Measurements in the longest strictly increasing run and the index of its first one
+    pub longest_run: usize,
+    pub longest_run_start: usize,
+    // The biggest change between two measurements, in absolute value, and the index before it
+    pub largest_jump: Option<(i64, usize)>,
+    pub histogram: BTreeMap<i64, usize>,
+}
+
+fn parse(reader: impl BufRead) -> impl Iterator<Item = usize> {
+    parsing::read_lines(reader)
+}
+
+// Two adjacent windows share all but one depth each, so the sum grows
+// exactly when the depth entering the window is deeper than the one leaving it
+pub fn increases(depths: impl Iterator<Item = usize>, window: usize) -> usize {
+    assert!(window >= 1, "The window must hold at least one depth");
+
+    // The depths can only be read once, so the last ones are kept around,
+    // never more than the window or than the depths seen so far
+    let mut last = VecDeque::new();
+    let mut count = 0;
+
+    for depth in depths {
+        if last.len() == window && depth > last.pop_front().unwrap() {
+            count += 1;
+        }
+        last.push_back(depth);
+    }
+
+    count
+}
+
+// Reads the input twice instead of keeping the window in memory
+pub fn count_increases(input: &str, window: usize) -> usize {
+    assert!(window >= 1, "The window must hold at least one depth");
+
+    let leaving = parse(input.as_bytes());
+    let entering = parse(input.as_bytes()).skip(window);
+
+    leaving
+        .zip(entering)
+        .filter(|(leaving, entering)| entering > leaving)
+        .count()
+}
+
+pub fn p1(input: &str) -> usize {
+    count_increases(input, 1)
+}
+
+pub fn p2(input: &str) -> usize {
+    count_increases(input, 3)
+}
+
+pub fn stream_p1(reader: impl BufRead) -> usize {
+    increases(parse(reader), 1)
+}
+
+pub fn stream_p2(reader: impl BufRead) -> usize {
+    increases(parse(reader), 3)
+}
+
+// Built in a single pass, so it works on streamed inputs as well
+pub fn report(reader: impl BufRead) -> Report {
+    let mut report = Report::default();
+    let mut previous: Option<usize> = None;
+    let mut run_start = 0;
+
+    for (i, depth) in parse(reader).enumerate() {
+        report.depths += 1;
+
+        if let Some(previous) = previous {
+            let delta = depth as i64 - previous as i64;
+
+            if delta > 0 {
+                report.increases += 1;
+            } else {
+                report.decreases += (delta < 0) as usize;
+                run_start = i;
+            }
+            if report
+                .largest_jump
+                .is_none_or(|(jump, _)| delta.abs() > jump.abs())
+            {
+                report.largest_jump = Some((delta, i - 1));
+            }
+            *report.histogram.entry(delta).or_default() += 1;
+        }
+
+        if i + 1 - run_start > report.longest_run {
+            report.longest_run = i + 1 - run_start;
+            report.longest_run_start = run_start;
+        }
+        previous = Some(depth);
+    }
+
+    report
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        let largest_jump = match self.largest_jump {
+            Some((delta, index)) => format!("{{\"delta\":{},\"index\":{}}}", delta, index),
+            None => "null".to_string(),
+        };
+        let histogram = self
+            .histogram
+            .iter()
+            .map(|(delta, count)| format!("\"{}\":{}", delta, count))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{{\"depths\":{},\"increases\":{},\"decreases\":{},\
+             \"longest_run\":{{\"length\":{},\"start\":{}}},\
+             \"largest_jump\":{},\"histogram\":{{{}}}}}",
+            self.depths,
+            self.increases,
+            self.decreases,
+            self.longest_run,
+            self.longest_run_start,
+            largest_jump,
+            histogram
+        )
+    }
+}
+
+#[test]
+fn test_report() {
+    let sweep = report(
+        "199
+200
+208
+210
+200
+207
+240
+269
+260
+263"
+        .as_bytes(),
+    );
+
+    assert_eq!(sweep.increases, 7);
+    assert_eq!(sweep.decreases, 2);
+    // Ties keep the first run
+    assert_eq!((sweep.longest_run, sweep.longest_run_start), (4, 0));
+    assert_eq!(sweep.largest_jump, Some((33, 5)));
+    assert_eq!(sweep.histogram.values().sum::<usize>(), 9);
+    assert_eq!(
+        sweep.to_json(),
+        "{\"depths\":10,\"increases\":7,\"decreases\":2,\"longest_run\":{\"length\":4,\"start\":0},\
+         \"largest_jump\":{\"delta\":33,\"index\":5},\"histogram\":{\"-10\":1,\"-9\":1,\"1\":1,\
+         \"2\":1,\"3\":1,\"7\":1,\"8\":1,\"29\":1,\"33\":1}}"
+    );
+
+    let empty = report("".as_bytes());
+    assert_eq!(empty.longest_run, 0);
+    assert_eq!(
+        empty.to_json(),
+        "{\"depths\":0,\"increases\":0,\"decreases\":0,\"longest_run\":{\"length\":0,\"start\":0},\
+         \"largest_jump\":null,\"histogram\":{}}"
+    );
+}
+
+#[test]
+fn test_count_increases() {
+    let depths = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9];
+    let input: Vec<String> = depths.iter().map(|d| d.to_string()).collect();
+
+    for window in 1..=depths.len() + 1 {
+        let sums: Vec<usize> = depths.windows(window).map(|w| w.iter().sum()).collect();
+        let expected = sums.windows(2).filter(|w| w[1] > w[0]).count();
+
+        assert_eq!(count_increases(&input.join("\n"), window), expected);
+        assert_eq!(increases(depths.iter().copied(), window), expected);
+    }
+
+    // Windows wider than the input need no memory
+    assert_eq!(count_increases("1\n2\n3", 1 << 40), 0);
+    assert_eq!(count_increases("1\n2\n3", usize::MAX), 0);
+    assert_eq!(increases([1, 2, 3].into_iter(), usize::MAX), 0);
+}
+
+#[test]
+fn test_stream() {
+    // Sonar logs generated on the fly, never held in memory as a whole. The expected counts
+    // compare each depth with the one a window later, generating the same log twice
+    use crate::parsing::Generated;
+    use crate::random::Lcg;
+
+    let depths = || {
+        let mut lcg = Lcg::new(1);
+        (0..100_000).map(move |i| lcg.below(1000) as usize + i / 10)
+    };
+    let log = || {
+        let bytes = depths().flat_map(|depth| format!("{}\n", depth).into_bytes());
+        std::io::BufReader::new(Generated(bytes))
+    };
+    let expected = |window| {
+        depths()
+            .zip(depths().skip(window))
+            .filter(|(leaving, entering)| entering > leaving)
+            .count()
+    };
+
+    assert_eq!(stream_p1(log()), expected(1));
+    assert_eq!(stream_p2(log()), expected(3));
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("199
+200
+208
+210
+200
+207
+240
+269
+260
+263"),
+        7
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("199
+200
+208
+210
+200
+207
+240
+269
+260
+263"),
+        5
+    );
+}
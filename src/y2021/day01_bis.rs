@@ -1,15 +1,15 @@
-fn parse(input: &str) -> Vec<usize> {
-    input.lines().map(|n| n.parse().unwrap()).collect()
+use super::day01::increases;
+
+fn parse<'a>(input: &'a str) -> impl Iterator<Item = usize> + 'a {
+    input.lines().map(|n| n.parse().unwrap())
 }
 
 pub fn p1(input: &str) -> usize {
-    parse(input).windows(2).filter(|w| w[1] > w[0]).count()
+    increases(parse(input), 1)
 }
 
 pub fn p2(input: &str) -> usize {
-    let sums: Vec<usize> = parse(input).windows(3).map(|w| w.iter().sum()).collect();
-
-    sums.windows(2).filter(|w| w[1] > w[0]).count()
+    increases(parse(input), 3)
 }
 
 #[test]
@@ -0,0 +1,731 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{BufRead, Lines};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Forward(usize),
+    Back(usize),
+    Up(usize),
+    Down(usize),
+    Turn(Side),
+}
+
+// A course is a list of statements, repeat blocks holding their own
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    // The move and the line it comes from
+    Move(usize, Move),
+    Repeat(usize, Vec<Statement>),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Heading {
+    #[default]
+    East,
+    North,
+    West,
+    South,
+}
+
+// x and z are horizontal, x growing to the east and z to the north, y is the depth
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Submarine {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+    pub aim: i64,
+    pub heading: Heading,
+}
+
+// What to do when a move takes the submarine above the surface or the aim below zero
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Underflow {
+    #[default]
+    Clamp,
+    AllowNegative,
+    Fail,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnderflowError {
+    pub line: usize,
+    // Where the offending command would have taken the submarine
+    pub submarine: Submarine,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Underflow(UnderflowError),
+}
+
+pub type Model = fn(Submarine, &Move) -> Submarine;
+
+// Words of the course with their line number, starting from 1. Comments start with '#'
+struct Tokens<R> {
+    lines: Lines<R>,
+    line: usize,
+    pending: VecDeque<String>,
+}
+
+// Parses the course one top level statement at a time, so only repeat blocks are held in memory
+pub struct Course<R> {
+    tokens: Tokens<R>,
+}
+
+impl<R: BufRead> Iterator for Tokens<R> {
+    type Item = (usize, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let l = self.lines.next()?.expect("Cannot read line");
+            self.line += 1;
+
+            let code = l.split('#').next().unwrap();
+            self.pending = code
+                .replace('{', " { ")
+                .replace('}', " } ")
+                .split_whitespace()
+                .map(String::from)
+                .collect();
+        }
+
+        Some((self.line, self.pending.pop_front().unwrap()))
+    }
+}
+
+impl<R: BufRead> Course<R> {
+    pub fn new(reader: R) -> Self {
+        Course {
+            tokens: Tokens {
+                lines: reader.lines(),
+                line: 0,
+                pending: VecDeque::new(),
+            },
+        }
+    }
+
+    fn error<T>(line: usize, message: String) -> Result<T, Error> {
+        Err(Error::Parse(ParseError { line, message }))
+    }
+
+    fn word(&mut self, line: usize, after: &str) -> Result<String, Error> {
+        match self.tokens.next() {
+            Some((_, word)) => Ok(word),
+            None => Self::error(line, format!("missing argument after `{}`", after)),
+        }
+    }
+
+    fn amount(&mut self, line: usize, command: &str) -> Result<usize, Error> {
+        let word = self.word(line, command)?;
+
+        word.parse().or_else(|_| {
+            Self::error(
+                line,
+                format!("`{}` expects a positive number, found `{}`", command, word),
+            )
+        })
+    }
+
+    fn statement(&mut self, line: usize, command: &str) -> Result<Statement, Error> {
+        let m = match command {
+            "forward" => Move::Forward(self.amount(line, command)?),
+            "back" => Move::Back(self.amount(line, command)?),
+            "up" => Move::Up(self.amount(line, command)?),
+            "down" => Move::Down(self.amount(line, command)?),
+            "turn" => match self.word(line, command)?.as_str() {
+                "left" => Move::Turn(Side::Left),
+                "right" => Move::Turn(Side::Right),
+                side => {
+                    return Self::error(
+                        line,
+                        format!("can only turn left or right, not `{}`", side),
+                    )
+                }
+            },
+            "repeat" => {
+                let times = self.amount(line, command)?;
+                if self.word(line, "repeat")? != "{" {
+                    return Self::error(line, "`repeat` expects a block in braces".to_string());
+                }
+
+                let mut block = Vec::new();
+                loop {
+                    match self.tokens.next() {
+                        Some((_, word)) if word == "}" => break,
+                        Some((l, word)) => block.push(self.statement(l, &word)?),
+                        None => {
+                            return Self::error(
+                                self.tokens.line,
+                                format!("the repeat block opened on line {} is not closed", line),
+                            )
+                        }
+                    }
+                }
+
+                return Ok(Statement::Repeat(times, block));
+            }
+            "}" => return Self::error(line, "`}` without a repeat block".to_string()),
+            other => return Self::error(line, format!("unknown command `{}`", other)),
+        };
+
+        Ok(Statement::Move(line, m))
+    }
+}
+
+impl<R: BufRead> Iterator for Course<R> {
+    type Item = Result<Statement, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line, command) = self.tokens.next()?;
+        Some(self.statement(line, &command))
+    }
+}
+
+impl Heading {
+    fn delta(self) -> (i64, i64) {
+        match self {
+            Heading::East => (1, 0),
+            Heading::North => (0, 1),
+            Heading::West => (-1, 0),
+            Heading::South => (0, -1),
+        }
+    }
+
+    fn turn(self, side: Side) -> Heading {
+        const COUNTER_CLOCKWISE: [Heading; 4] =
+            [Heading::East, Heading::North, Heading::West, Heading::South];
+
+        let i = COUNTER_CLOCKWISE.iter().position(|&h| h == self).unwrap();
+        match side {
+            Side::Left => COUNTER_CLOCKWISE[(i + 1) % 4],
+            Side::Right => COUNTER_CLOCKWISE[(i + 3) % 4],
+        }
+    }
+}
+
+impl Statement {
+    // `record` sees the submarine after every move
+    fn run<F>(
+        &self,
+        submarine: Submarine,
+        model: Model,
+        underflow: Underflow,
+        record: &mut F,
+    ) -> Result<Submarine, Error>
+    where
+        F: FnMut(&Submarine),
+    {
+        match self {
+            Statement::Move(line, m) => {
+                let mut next = model(submarine, m);
+
+                if next.y < 0 || next.aim < 0 {
+                    match underflow {
+                        Underflow::Clamp => {
+                            next.y = next.y.max(0);
+                            next.aim = next.aim.max(0);
+                        }
+                        Underflow::AllowNegative => (),
+                        Underflow::Fail => {
+                            return Err(Error::Underflow(UnderflowError {
+                                line: *line,
+                                submarine: next,
+                            }))
+                        }
+                    }
+                }
+
+                record(&next);
+                Ok(next)
+            }
+            Statement::Repeat(times, block) => (0..*times).try_fold(submarine, |submarine, _| {
+                block.iter().try_fold(submarine, |submarine, s| {
+                    s.run(submarine, model, underflow, record)
+                })
+            }),
+        }
+    }
+}
+
+pub fn navigate(
+    reader: impl BufRead,
+    model: Model,
+    underflow: Underflow,
+) -> Result<Submarine, Error> {
+    Course::new(reader).try_fold(Submarine::default(), |submarine, statement| {
+        statement?.run(submarine, model, underflow, &mut |_| ())
+    })
+}
+
+// Every position of the submarine, starting from the surface
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    pub steps: Vec<Submarine>,
+}
+
+impl Trajectory {
+    pub fn record(
+        reader: impl BufRead,
+        model: Model,
+        underflow: Underflow,
+    ) -> Result<Trajectory, Error> {
+        let mut steps = vec![Submarine::default()];
+
+        Course::new(reader).try_fold(Submarine::default(), |submarine, statement| {
+            statement?.run(submarine, model, underflow, &mut |s| steps.push(*s))
+        })?;
+
+        Ok(Trajectory { steps })
+    }
+
+    // The deepest point and the first step reaching it
+    pub fn max_depth(&self) -> (i64, usize) {
+        self.steps
+            .iter()
+            .enumerate()
+            .fold((i64::MIN, 0), |(max, at), (step, submarine)| {
+                if submarine.y > max {
+                    (submarine.y, step)
+                } else {
+                    (max, at)
+                }
+            })
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,x,z,depth,aim\n");
+        for (step, s) in self.steps.iter().enumerate() {
+            csv.push_str(&format!("{},{},{},{},{}\n", step, s.x, s.z, s.y, s.aim));
+        }
+
+        csv
+    }
+}
+
+impl Submarine {
+    // Moves along the heading, backwards for negative distances
+    fn advance(self, distance: i64) -> Submarine {
+        let (dx, dz) = self.heading.delta();
+
+        Submarine {
+            x: self.x + dx * distance,
+            z: self.z + dz * distance,
+            ..self
+        }
+    }
+}
+
+pub fn make_move(submarine: Submarine, m: &Move) -> Submarine {
+    match *m {
+        Move::Forward(n) => submarine.advance(n as i64),
+        Move::Back(n) => submarine.advance(-(n as i64)),
+        Move::Up(n) => Submarine {
+            y: submarine.y - n as i64,
+            ..submarine
+        },
+        Move::Down(n) => Submarine {
+            y: submarine.y + n as i64,
+            ..submarine
+        },
+        Move::Turn(side) => Submarine {
+            heading: submarine.heading.turn(side),
+            ..submarine
+        },
+    }
+}
+
+pub fn p1(input: &str) -> i64 {
+    stream_p1(input.as_bytes())
+}
+
+pub fn stream_p1(reader: impl BufRead) -> i64 {
+    let submarine = navigate(reader, make_move, Underflow::Clamp).unwrap();
+
+    submarine.x * submarine.y
+}
+
+// Going back also undoes the depth change of going forward
+pub fn make_move2(submarine: Submarine, m: &Move) -> Submarine {
+    let dive = |submarine: Submarine, distance: i64| Submarine {
+        y: submarine.y + distance * submarine.aim,
+        ..submarine.advance(distance)
+    };
+
+    match *m {
+        Move::Forward(n) => dive(submarine, n as i64),
+        Move::Back(n) => dive(submarine, -(n as i64)),
+        Move::Up(n) => Submarine {
+            aim: submarine.aim - n as i64,
+            ..submarine
+        },
+        Move::Down(n) => Submarine {
+            aim: submarine.aim + n as i64,
+            ..submarine
+        },
+        Move::Turn(_) => make_move(submarine, m),
+    }
+}
+
+pub fn p2(input: &str) -> i64 {
+    stream_p2(input.as_bytes())
+}
+
+pub fn stream_p2(reader: impl BufRead) -> i64 {
+    let submarine = navigate(reader, make_move2, Underflow::Clamp).unwrap();
+
+    submarine.x * submarine.y
+}
+
+// Checks a plan by replaying it, with nothing clamped
+fn checked(moves: Vec<Move>, model: Model, (x, depth): (i64, i64)) -> Vec<Move> {
+    let reached = moves.iter().fold(Submarine::default(), model);
+    assert_eq!(
+        (reached.x, reached.y),
+        (x, depth),
+        "The plan misses its target"
+    );
+
+    moves
+}
+
+// Back for negative distances
+fn forward(distance: i64) -> Move {
+    if distance < 0 {
+        Move::Back(distance.unsigned_abs() as usize)
+    } else {
+        Move::Forward(distance as usize)
+    }
+}
+
+// Up for negative distances
+fn down(distance: i64) -> Move {
+    if distance < 0 {
+        Move::Up(distance.unsigned_abs() as usize)
+    } else {
+        Move::Down(distance as usize)
+    }
+}
+
+// The fewest moves to reach the target with the part 1 model, the depth may be above the surface
+pub fn plan_p1(x: i64, depth: i64) -> Vec<Move> {
+    let moves = [
+        (x != 0).then(|| forward(x)),
+        (depth != 0).then(|| down(depth)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    checked(moves, make_move, (x, depth))
+}
+
+// The fewest moves to reach the target with the aim model. Two moves are enough when x divides
+// the depth, otherwise the last step can dive with an aim of the whole depth, and without going
+// anywhere along x, turning lets that step go north instead
+pub fn plan_p2(x: i64, depth: i64) -> Vec<Move> {
+    let moves = match (x, depth) {
+        (0, 0) => vec![],
+        (x, 0) => vec![forward(x)],
+        (0, depth) => vec![down(depth), Move::Turn(Side::Left), Move::Forward(1)],
+        (x, depth) if depth % x == 0 => vec![down(depth / x), forward(x)],
+        (x, depth) => {
+            let last = x.signum();
+            vec![forward(x - last), down(depth * last), forward(last)]
+        }
+    };
+
+    checked(moves, make_move2, (x, depth))
+}
+
+pub fn trajectory_csv(input: &str, model: Model) -> Result<String, Error> {
+    Trajectory::record(input.as_bytes(), model, Underflow::Clamp).map(|t| t.to_csv())
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Forward(n) => write!(f, "forward {}", n),
+            Move::Back(n) => write!(f, "back {}", n),
+            Move::Up(n) => write!(f, "up {}", n),
+            Move::Down(n) => write!(f, "down {}", n),
+            Move::Turn(Side::Left) => write!(f, "turn left"),
+            Move::Turn(Side::Right) => write!(f, "turn right"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "Line {}: {}", err.line, err.message),
+            Error::Underflow(err) => write!(
+                f,
+                "Line {}: the submarine would reach depth {} with aim {}",
+                err.line, err.submarine.y, err.submarine.aim
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_underflow() {
+    let course = "up 3\n\ndown 1\nforward 2";
+
+    let clamped = navigate(course.as_bytes(), make_move, Underflow::Clamp).unwrap();
+    assert_eq!((clamped.x, clamped.y), (2, 1));
+    let negative = navigate(course.as_bytes(), make_move, Underflow::AllowNegative).unwrap();
+    assert_eq!((negative.x, negative.y), (2, -2));
+    assert_eq!(
+        navigate(course.as_bytes(), make_move, Underflow::Fail)
+            .unwrap_err()
+            .to_string(),
+        "Line 1: the submarine would reach depth -3 with aim 0"
+    );
+
+    // The empty line still counts
+    let course = "forward 1\n\nup 2\nforward 3";
+    let negative = navigate(course.as_bytes(), make_move2, Underflow::AllowNegative).unwrap();
+    assert_eq!((negative.x, negative.y, negative.aim), (4, -6, -2));
+    assert_eq!(
+        navigate(course.as_bytes(), make_move2, Underflow::Fail),
+        Err(Error::Underflow(UnderflowError {
+            line: 3,
+            submarine: Submarine {
+                x: 1,
+                y: 0,
+                aim: -2,
+                ..Submarine::default()
+            }
+        }))
+    );
+}
+
+#[test]
+fn test_course() {
+    let course = "# Square dive
+repeat 4 {
+    forward 2 # One side
+    down 1
+    turn left
+}
+repeat 2 { back 3 repeat 2 { up 1 } }";
+
+    let submarine = navigate(course.as_bytes(), make_move, Underflow::Fail).unwrap();
+    assert_eq!(
+        (submarine.x, submarine.z, submarine.y, submarine.heading),
+        (-6, 0, 0, Heading::East)
+    );
+
+    let submarine = navigate(course.as_bytes(), make_move2, Underflow::AllowNegative).unwrap();
+    // Forward 2 with aims 0 to 3, then back 3 with aims 4 and 2
+    assert_eq!(
+        (submarine.y, submarine.aim),
+        (2 * (1 + 2 + 3) - 3 * (4 + 2), 0)
+    );
+
+    assert_eq!(
+        Course::new("forward 1\nrepeat 2 {\nup 1".as_bytes())
+            .nth(1)
+            .unwrap()
+            .unwrap_err()
+            .to_string(),
+        "Line 3: the repeat block opened on line 2 is not closed"
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |course: &str| {
+        navigate(course.as_bytes(), make_move, Underflow::Clamp)
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(error("forward 1\njump 2"), "Line 2: unknown command `jump`");
+    assert_eq!(error("down"), "Line 1: missing argument after `down`");
+    assert_eq!(
+        error("up -1"),
+        "Line 1: `up` expects a positive number, found `-1`"
+    );
+    assert_eq!(
+        error("turn around"),
+        "Line 1: can only turn left or right, not `around`"
+    );
+    assert_eq!(
+        error("repeat 2 forward 1"),
+        "Line 1: `repeat` expects a block in braces"
+    );
+    assert_eq!(error("\n}"), "Line 2: `}` without a repeat block");
+}
+
+#[test]
+fn test_trajectory() {
+    let course = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    let trajectory = Trajectory::record(course.as_bytes(), make_move2, Underflow::Fail).unwrap();
+    assert_eq!(trajectory.steps.len(), 7);
+    assert_eq!(trajectory.max_depth(), (60, 6));
+    assert_eq!(
+        trajectory.to_csv(),
+        "step,x,z,depth,aim
+0,0,0,0,0
+1,5,0,0,0
+2,5,0,0,5
+3,13,0,40,5
+4,13,0,40,2
+5,13,0,40,10
+6,15,0,60,10
+"
+    );
+
+    // Moves inside repeat blocks are recorded one by one
+    let trajectory =
+        Trajectory::record("repeat 3 { down 2 }".as_bytes(), make_move, Underflow::Fail).unwrap();
+    assert_eq!(trajectory.max_depth(), (6, 3));
+
+    assert!(trajectory_csv(course, make_move)
+        .unwrap()
+        .ends_with("6,15,0,10,0\n"));
+    assert_eq!(
+        trajectory_csv("forward 5\nsideways 2", make_move)
+            .unwrap_err()
+            .to_string(),
+        "Line 2: unknown command `sideways`"
+    );
+}
+
+#[test]
+fn test_planner() {
+    let course = |moves: Vec<Move>| {
+        moves
+            .iter()
+            .map(Move::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    assert_eq!(plan_p1(15, 10), [Move::Forward(15), Move::Down(10)]);
+    assert_eq!(plan_p1(0, 0), []);
+    assert_eq!(plan_p1(-1, 0), [Move::Back(1)]);
+    assert_eq!(plan_p1(0, -1), [Move::Up(1)]);
+
+    assert_eq!(plan_p2(15, 60), [Move::Down(4), Move::Forward(15)]);
+    assert_eq!(
+        plan_p2(15, 61),
+        [Move::Forward(14), Move::Down(61), Move::Forward(1)]
+    );
+    assert_eq!(
+        plan_p2(0, 3),
+        [Move::Down(3), Move::Turn(Side::Left), Move::Forward(1)]
+    );
+    assert_eq!(
+        plan_p2(-15, 61),
+        [Move::Back(14), Move::Up(61), Move::Back(1)]
+    );
+
+    // Replayed without clamping since targets may be above the surface
+    let reached = |plan: Vec<Move>, model| {
+        let submarine = navigate(course(plan).as_bytes(), model, Underflow::AllowNegative).unwrap();
+        (submarine.x, submarine.y)
+    };
+    for x in -20..20 {
+        for depth in -50..50 {
+            let plan = plan_p1(x, depth);
+            assert_eq!(plan.len(), (x != 0) as usize + (depth != 0) as usize);
+            assert_eq!(reached(plan, make_move), (x, depth));
+
+            // Only a single move forward can keep the aim at zero, and only two can dive
+            let fewest = match (x, depth) {
+                (0, 0) => 0,
+                (_, 0) => 1,
+                (0, _) => 3,
+                _ if depth % x == 0 => 2,
+                _ => 3,
+            };
+            let plan = plan_p2(x, depth);
+            assert_eq!(plan.len(), fewest);
+            assert_eq!(reached(plan, make_move2), (x, depth));
+        }
+    }
+}
+
+#[test]
+fn test_stream() {
+    // Random commands read through a tiny buffer, so lines are split between reads,
+    // checked against a plain replay of the same commands
+    use std::io::BufReader;
+
+    use crate::parsing::Generated;
+    use crate::random::Lcg;
+
+    let commands = || {
+        let mut lcg = Lcg::new(2);
+        (0..50_000).map(move |_| {
+            let command = ["forward", "down", "up"][lcg.below(3) as usize];
+            (command, lcg.below(9) as i64 + 1)
+        })
+    };
+    let log = || {
+        let bytes = commands().flat_map(|(command, n)| format!("{} {}\n", command, n).into_bytes());
+        BufReader::with_capacity(7, Generated(bytes))
+    };
+
+    let (x, depth) = commands().fold((0, 0), |(x, depth), command| match command {
+        ("forward", n) => (x + n, depth),
+        ("down", n) => (x, depth + n),
+        (_, n) => (x, (depth - n).max(0)),
+    });
+    assert_eq!(stream_p1(log()), x * depth);
+
+    let (x, depth, _) = commands().fold((0, 0, 0), |(x, depth, aim), command| match command {
+        ("forward", n) => (x + n, depth + aim * n, aim),
+        ("down", n) => (x, depth, aim + n),
+        (_, n) => (x, depth, (aim - n).max(0)),
+    });
+    assert_eq!(stream_p2(log()), x * depth);
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2"),
+        150
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2"),
+        900
+    );
+}
@@ -0,0 +1,545 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// One bit per number of the report, packed in words
+type Bitset = Vec<u64>;
+
+// Stored column by column, so counting the ones of a column among any subset
+// of the numbers is a matter of and-ing and popcounting words
+#[derive(Debug)]
+struct Diagnostic {
+    len: usize,
+    // The first column is the most significant bit
+    columns: Vec<Bitset>,
+}
+
+// Numbers sharing a prefix share the path to it, every node counting the numbers below it
+#[derive(Debug)]
+pub struct Trie {
+    width: usize,
+    // The root is the first node, children refer to nodes by their index
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    count: usize,
+    children: [Option<usize>; 2],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Common {
+    Most,
+    Least,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    Zero,
+    One,
+    Fail,
+}
+
+// Which bit the numbers must have in a column to be kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub common: Common,
+    pub tie: TieBreak,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TieError {
+    pub column: usize,
+    // Numbers left with each bit
+    pub count: usize,
+}
+
+// Explains how the answers are found, empty without any number
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    // Zeros and ones of each column
+    pub columns: Vec<(usize, usize)>,
+    pub gamma: String,
+    pub epsilon: String,
+    // Numbers left after filtering each column, until a single one remains
+    pub oxygen_steps: Vec<usize>,
+    pub co2_steps: Vec<usize>,
+    pub oxygen: String,
+    pub co2: String,
+}
+
+fn parse(input: &str) -> Diagnostic {
+    let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let width = lines.first().map_or(0, |l| l.len());
+    let mut columns = vec![vec![0; lines.len().div_ceil(64)]; width];
+
+    for (i, line) in lines.iter().enumerate() {
+        assert_eq!(line.len(), width, "Numbers must all have the same width");
+
+        for (column, &bit) in line.iter().enumerate() {
+            match bit {
+                b'1' => columns[column][i / 64] |= 1 << (i % 64),
+                b'0' => (),
+                _ => panic!("Invalid bit {}", bit as char),
+            }
+        }
+    }
+
+    Diagnostic {
+        len: lines.len(),
+        columns,
+    }
+}
+
+pub fn p1(input: &str) -> u64 {
+    let diagnostic = parse(input);
+    let gamma = diagnostic.gamma();
+    let epsilon = !gamma & diagnostic.mask();
+
+    gamma * epsilon
+}
+
+// The bits of the value, one per column
+fn bits(value: u64, width: usize) -> String {
+    (0..width)
+        .rev()
+        .map(|bit| if value >> bit & 1 == 1 { '1' } else { '0' })
+        .collect()
+}
+
+pub fn report(input: &str) -> Report {
+    let diagnostic = parse(input);
+    if diagnostic.len == 0 {
+        return Report::default();
+    }
+
+    let lines: Vec<&str> = input.lines().collect();
+    let everything = diagnostic.everything();
+
+    let gamma = diagnostic.gamma();
+    let epsilon = !gamma & diagnostic.mask();
+    let (oxygen, oxygen_steps) = diagnostic.rating(|_| Rule::OXYGEN).unwrap();
+    let (co2, co2_steps) = diagnostic.rating(|_| Rule::CO2).unwrap();
+
+    Report {
+        columns: (0..diagnostic.width())
+            .map(|column| {
+                let ones = diagnostic.ones(column, &everything);
+                (diagnostic.len - ones, ones)
+            })
+            .collect(),
+        gamma: bits(gamma, diagnostic.width()),
+        epsilon: bits(epsilon, diagnostic.width()),
+        oxygen_steps,
+        co2_steps,
+        oxygen: lines[oxygen].to_string(),
+        co2: lines[co2].to_string(),
+    }
+}
+
+pub fn p2(input: &str) -> u64 {
+    let trie = Trie::new(input);
+    let rating = |rule: Rule| {
+        let bits = trie.rating(|_| rule).unwrap();
+        u64::from_str_radix(&bits, 2).unwrap()
+    };
+
+    rating(Rule::OXYGEN) * rating(Rule::CO2)
+}
+
+impl Rule {
+    pub const OXYGEN: Rule = Rule {
+        common: Common::Most,
+        tie: TieBreak::One,
+    };
+    pub const CO2: Rule = Rule {
+        common: Common::Least,
+        tie: TieBreak::Zero,
+    };
+
+    // When all the numbers left share the bit, the least common one would match none of them,
+    // so that bit is kept whatever the rule
+    pub fn bit(&self, column: usize, zeros: usize, ones: usize) -> Result<bool, TieError> {
+        if zeros == 0 || ones == 0 {
+            return Ok(ones > 0);
+        }
+
+        match (ones.cmp(&zeros), self.tie) {
+            (Ordering::Equal, TieBreak::Zero) => Ok(false),
+            (Ordering::Equal, TieBreak::One) => Ok(true),
+            (Ordering::Equal, TieBreak::Fail) => Err(TieError {
+                column,
+                count: ones,
+            }),
+            (Ordering::Greater, _) => Ok(self.common == Common::Most),
+            (Ordering::Less, _) => Ok(self.common == Common::Least),
+        }
+    }
+}
+
+impl Diagnostic {
+    fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    fn everything(&self) -> Bitset {
+        let mut all = vec![u64::MAX; self.len.div_ceil(64)];
+        if !self.len.is_multiple_of(64) {
+            *all.last_mut().unwrap() = (1 << (self.len % 64)) - 1;
+        }
+
+        all
+    }
+
+    fn ones(&self, column: usize, candidates: &Bitset) -> usize {
+        self.columns[column]
+            .iter()
+            .zip(candidates)
+            .map(|(bits, candidates)| (bits & candidates).count_ones() as usize)
+            .sum()
+    }
+
+    // The most common bit of each column, ties going to 1
+    fn gamma(&self) -> u64 {
+        assert!(self.width() <= 64, "Numbers are too wide to fit in 64 bits");
+        let everything = self.everything();
+
+        (0..self.width()).fold(0, |acc, column| {
+            let ones = self.ones(column, &everything);
+            let bit = Rule::OXYGEN.bit(column, self.len - ones, ones).unwrap();
+            acc << 1 | bit as u64
+        })
+    }
+
+    // Ones over the width of the numbers
+    fn mask(&self) -> u64 {
+        u64::MAX.checked_shr(64 - self.width() as u32).unwrap_or(0)
+    }
+
+    // Keeps the numbers following the rule of each column until a single one is left,
+    // and returns its index along with the numbers left after each step
+    fn rating<F>(&self, rules: F) -> Result<(usize, Vec<usize>), TieError>
+    where
+        F: Fn(usize) -> Rule,
+    {
+        let mut candidates = self.everything();
+        let mut left = self.len;
+        let mut steps = Vec::new();
+
+        for column in 0..self.width() {
+            if left <= 1 {
+                break;
+            }
+
+            let ones = self.ones(column, &candidates);
+            let keep_ones = rules(column).bit(column, left - ones, ones)?;
+
+            for (candidates, bits) in candidates.iter_mut().zip(&self.columns[column]) {
+                *candidates &= if keep_ones { *bits } else { !bits };
+            }
+            left = if keep_ones { ones } else { left - ones };
+            steps.push(left);
+        }
+
+        let word = candidates.iter().position(|&w| w != 0).unwrap();
+        Ok((
+            word * 64 + candidates[word].trailing_zeros() as usize,
+            steps,
+        ))
+    }
+}
+
+impl Trie {
+    pub fn new(input: &str) -> Self {
+        let mut trie = Trie {
+            width: 0,
+            nodes: vec![Node::default()],
+        };
+
+        for (i, line) in input.lines().enumerate() {
+            if i == 0 {
+                trie.width = line.len();
+            }
+            assert_eq!(
+                line.len(),
+                trie.width,
+                "Numbers must all have the same width"
+            );
+
+            let mut node = 0;
+            trie.nodes[node].count += 1;
+            for bit in line.chars() {
+                let bit = bit.to_digit(2).expect("Invalid bit") as usize;
+
+                node = match trie.nodes[node].children[bit] {
+                    Some(child) => child,
+                    None => {
+                        trie.nodes.push(Node::default());
+                        trie.nodes[node].children[bit] = Some(trie.nodes.len() - 1);
+                        trie.nodes.len() - 1
+                    }
+                };
+                trie.nodes[node].count += 1;
+            }
+        }
+
+        trie
+    }
+
+    fn count(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |node| self.nodes[node].count)
+    }
+
+    // How many numbers start with the given bits
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        let node = prefix.chars().try_fold(0, |node, bit| {
+            let bit = bit.to_digit(2)? as usize;
+            self.nodes[node].children[bit]
+        });
+
+        self.count(node)
+    }
+
+    // A single walk down the trie, following the rule of each column
+    pub fn rating<F>(&self, rules: F) -> Result<String, TieError>
+    where
+        F: Fn(usize) -> Rule,
+    {
+        let mut node = 0;
+        let mut bits = String::with_capacity(self.width);
+
+        while bits.len() < self.width {
+            let [zeros, ones] = self.nodes[node].children;
+            let one = rules(bits.len()).bit(bits.len(), self.count(zeros), self.count(ones))?;
+
+            node = if one { ones } else { zeros }.unwrap();
+            bits.push(if one { '1' } else { '0' });
+        }
+
+        Ok(bits)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Values only fit for reports up to 64 columns
+        let number = |bits: &str| match u64::from_str_radix(bits, 2) {
+            Ok(value) => format!("{} ({})", bits, value),
+            Err(_) => bits.to_string(),
+        };
+        let steps = |steps: &[usize]| {
+            let total = self.columns.first().map_or(0, |(zeros, ones)| zeros + ones);
+            std::iter::once(total)
+                .chain(steps.iter().copied())
+                .map(|left| left.to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        };
+
+        writeln!(f, "column  zeros   ones")?;
+        for (column, (zeros, ones)) in self.columns.iter().enumerate() {
+            writeln!(f, "{:>6} {:>6} {:>6}", column, zeros, ones)?;
+        }
+        writeln!(f, "gamma   {}", number(&self.gamma))?;
+        writeln!(f, "epsilon {}", number(&self.epsilon))?;
+        writeln!(
+            f,
+            "oxygen  {}: {}",
+            steps(&self.oxygen_steps),
+            number(&self.oxygen)
+        )?;
+        writeln!(
+            f,
+            "co2     {}: {}",
+            steps(&self.co2_steps),
+            number(&self.co2)
+        )
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+#[test]
+fn test_wide_report() {
+    // 100 random numbers of 70 bits each, spanning several words both ways
+    use crate::random::Lcg;
+
+    let mut lcg = Lcg::new(3);
+    let lines: Vec<String> = (0..100)
+        .map(|_| (0..70).map(|_| ['0', '1'][lcg.below(2) as usize]).collect())
+        .collect();
+    let input = lines.join("\n");
+    let diagnostic = parse(&input);
+    let trie = Trie::new(&input);
+
+    assert_eq!(diagnostic.width(), 70);
+    for column in 0..70 {
+        let expected = lines
+            .iter()
+            .filter(|l| l.as_bytes()[column] == b'1')
+            .count();
+        assert_eq!(diagnostic.ones(column, &diagnostic.everything()), expected);
+    }
+
+    // Same filtering as the puzzle describes it, on the text
+    for (rule, most) in [(Rule::OXYGEN, true), (Rule::CO2, false)] {
+        let mut left: Vec<&String> = lines.iter().collect();
+        for column in 0..70 {
+            if left.len() > 1 {
+                let ones = left.iter().filter(|l| l.as_bytes()[column] == b'1').count();
+                let keep = if (ones >= left.len() - ones) == most {
+                    b'1'
+                } else {
+                    b'0'
+                };
+                if left.iter().any(|l| l.as_bytes()[column] == keep) {
+                    left.retain(|l| l.as_bytes()[column] == keep);
+                }
+            }
+        }
+
+        assert_eq!(&lines[diagnostic.rating(|_| rule).unwrap().0], left[0]);
+        assert_eq!(&trie.rating(|_| rule).unwrap(), left[0]);
+    }
+}
+
+#[test]
+fn test_trie() {
+    let trie = Trie::new(EXAMPLE);
+
+    assert_eq!(trie.count_prefix(""), 12);
+    assert_eq!(trie.count_prefix("1"), 7);
+    assert_eq!(trie.count_prefix("101"), 3);
+    assert_eq!(trie.count_prefix("10111"), 1);
+    assert_eq!(trie.count_prefix("0000"), 0);
+    assert_eq!(trie.count_prefix("1x"), 0);
+
+    assert_eq!(trie.rating(|_| Rule::OXYGEN), Ok("10111".to_string()));
+    assert_eq!(trie.rating(|_| Rule::CO2), Ok("01010".to_string()));
+}
+
+#[test]
+fn test_rules() {
+    let diagnostic = parse(EXAMPLE);
+    let trie = Trie::new(EXAMPLE);
+    let lines: Vec<&str> = EXAMPLE.lines().collect();
+
+    let most_or_zero = Rule {
+        common: Common::Most,
+        tie: TieBreak::Zero,
+    };
+    let strict = Rule {
+        common: Common::Least,
+        tie: TieBreak::Fail,
+    };
+    // Oxygen on the first two columns then CO2
+    let mixed = |column| if column < 2 { Rule::OXYGEN } else { Rule::CO2 };
+
+    for rules in [
+        &(|_| Rule::OXYGEN) as &dyn Fn(usize) -> Rule,
+        &|_| Rule::CO2,
+        &|_| most_or_zero,
+        &mixed,
+    ] {
+        // Both engines pick the same numbers
+        let (index, _) = diagnostic.rating(rules).unwrap();
+        assert_eq!(Ok(lines[index].to_string()), trie.rating(rules));
+    }
+
+    // 10110 and 10111 are left for the last column with the oxygen rule
+    assert_eq!(trie.rating(|_| most_or_zero), Ok("10110".to_string()));
+    // 10000 is the only one with a 0 in the third column among the 4 left
+    assert_eq!(trie.rating(mixed), Ok("10000".to_string()));
+    // 01111 and 01010 tie on the third column
+    assert_eq!(
+        diagnostic.rating(|_| strict).map(|(index, _)| index),
+        Err(TieError {
+            column: 2,
+            count: 1
+        })
+    );
+}
+
+#[test]
+fn test_report() {
+    let example = report(EXAMPLE);
+
+    assert_eq!(example.columns[0], (5, 7));
+    assert_eq!(example.oxygen_steps, [7, 4, 3, 2, 1]);
+    assert_eq!(example.co2_steps, [5, 2, 1]);
+    assert_eq!(
+        example.to_string(),
+        "column  zeros   ones
+     0      5      7
+     1      7      5
+     2      4      8
+     3      5      7
+     4      7      5
+gamma   10110 (22)
+epsilon 01001 (9)
+oxygen  12 -> 7 -> 4 -> 3 -> 2 -> 1: 10111 (23)
+co2     12 -> 5 -> 2 -> 1: 01010 (10)
+"
+    );
+
+    assert_eq!(report(""), Report::default());
+    let single = report("011");
+    assert_eq!(
+        (single.gamma.as_str(), single.epsilon.as_str()),
+        ("011", "100")
+    );
+    assert_eq!(
+        (single.oxygen_steps, single.oxygen),
+        (vec![], "011".to_string())
+    );
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010"),
+        198
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010"),
+        230
+    );
+}
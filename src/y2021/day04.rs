@@ -0,0 +1,595 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::mem;
+
+use crate::repl::Simulation;
+
+// A board wins once any of the lines of its rules is fully marked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Win {
+    Rows,
+    Columns,
+    // Only square boards have diagonals
+    Diagonals,
+    Corners,
+    Full,
+}
+
+pub const CLASSIC: &[Win] = &[Win::Rows, Win::Columns];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug)]
+struct Board {
+    width: usize,
+    height: usize,
+    // Numbers row by row
+    numbers: Vec<usize>,
+    marked: Vec<bool>,
+    // Marked cells of each line, so a draw only checks the lines going through its cell
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    diagonals: [usize; 2],
+    corners: usize,
+    count: usize,
+    unmarked_sum: usize,
+}
+
+#[derive(Debug)]
+pub struct Bingo {
+    numbers: Vec<usize>,
+    drawn: usize,
+    boards: Vec<Board>,
+    rules: Vec<Win>,
+    // Boards and cells where each number is, so a draw only touches the boards holding it
+    index: HashMap<usize, Vec<(usize, usize)>>,
+    won: Vec<bool>,
+    // Boards in the order they won, with their score
+    winners: Vec<(usize, usize)>,
+}
+
+fn error<T>(line: usize, message: String) -> Result<T, ParseError> {
+    Err(ParseError { line, message })
+}
+
+fn parse_numbers(line: usize, text: &str, separator: char) -> Result<Vec<usize>, ParseError> {
+    text.split(separator)
+        .filter(|n| !n.is_empty())
+        .map(|n| match n.trim().parse() {
+            Ok(n) => Ok(n),
+            Err(_) => error(line, format!("invalid number '{}'", n.trim())),
+        })
+        .collect()
+}
+
+// Boards are separated by blank lines and may have any size, as long as they are rectangular
+fn parse(input: &str) -> Result<(Vec<usize>, Vec<Board>), ParseError> {
+    let mut lines = input.lines().map(str::trim).enumerate();
+    let numbers = match lines.next() {
+        Some((_, first)) => parse_numbers(1, first, ',')?,
+        None => return error(1, "no numbers to draw".to_string()),
+    };
+
+    let mut boards = Vec::new();
+    let mut rows = Vec::new();
+    for (i, text) in lines {
+        if text.is_empty() {
+            if !rows.is_empty() {
+                boards.push(Board::new(mem::take(&mut rows))?);
+            }
+        } else {
+            rows.push((i + 1, parse_numbers(i + 1, text, ' ')?));
+        }
+    }
+    if !rows.is_empty() {
+        boards.push(Board::new(rows)?);
+    }
+
+    if boards.is_empty() {
+        return error(input.lines().count(), "no boards".to_string());
+    }
+    Ok((numbers, boards))
+}
+
+pub fn p1(input: &str) -> usize {
+    Bingo::new(input, CLASSIC).unwrap().play()[0].1
+}
+
+pub fn p2(input: &str) -> usize {
+    Bingo::new(input, CLASSIC).unwrap().play().last().unwrap().1
+}
+
+impl Board {
+    // Rows come with their line number
+    fn new(rows: Vec<(usize, Vec<usize>)>) -> Result<Self, ParseError> {
+        let width = rows[0].1.len();
+        let mut seen = HashSet::new();
+
+        for (line, row) in &rows {
+            if row.len() != width {
+                return error(
+                    *line,
+                    format!("expected {} numbers, found {}", width, row.len()),
+                );
+            }
+            if let Some(n) = row.iter().find(|&&n| !seen.insert(n)) {
+                return error(*line, format!("{} is already on the board", n));
+            }
+        }
+
+        let numbers: Vec<usize> = rows.into_iter().flat_map(|(_, row)| row).collect();
+        let height = numbers.len() / width;
+
+        Ok(Board {
+            width,
+            height,
+            marked: vec![false; numbers.len()],
+            rows: vec![0; height],
+            columns: vec![0; width],
+            diagonals: [0; 2],
+            corners: 0,
+            count: 0,
+            unmarked_sum: numbers.iter().sum(),
+            numbers,
+        })
+    }
+
+    // Returns whether marking the cell makes the board win
+    fn mark(&mut self, cell: usize, rules: &[Win]) -> bool {
+        if self.marked[cell] {
+            return false;
+        }
+        self.marked[cell] = true;
+        self.count += 1;
+        self.unmarked_sum -= self.numbers[cell];
+
+        let (w, h) = (self.width, self.height);
+        let (x, y) = (cell % w, cell / w);
+        let diagonals = [w == h && x == y, w == h && x + y == w - 1];
+        let corner = (x == 0 || x == w - 1) && (y == 0 || y == h - 1);
+
+        self.rows[y] += 1;
+        self.columns[x] += 1;
+        for (count, _) in self
+            .diagonals
+            .iter_mut()
+            .zip(diagonals)
+            .filter(|&(_, on)| on)
+        {
+            *count += 1;
+        }
+        self.corners += corner as usize;
+
+        rules.iter().any(|win| match win {
+            Win::Rows => self.rows[y] == w,
+            Win::Columns => self.columns[x] == h,
+            Win::Diagonals => (0..2).any(|d| diagonals[d] && self.diagonals[d] == w),
+            // Corners are shared on boards a single cell wide or high
+            Win::Corners => {
+                corner && self.corners == (1 + (w > 1) as usize) * (1 + (h > 1) as usize)
+            }
+            Win::Full => self.count == self.numbers.len(),
+        })
+    }
+}
+
+impl Bingo {
+    pub fn new(input: &str, rules: &[Win]) -> Result<Self, ParseError> {
+        let (numbers, boards) = parse(input)?;
+
+        let mut index: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+        for (i, board) in boards.iter().enumerate() {
+            for (cell, &n) in board.numbers.iter().enumerate() {
+                index.entry(n).or_default().push((i, cell));
+            }
+        }
+
+        Ok(Bingo {
+            numbers,
+            drawn: 0,
+            won: vec![false; boards.len()],
+            boards,
+            rules: rules.to_vec(),
+            index,
+            winners: Vec::new(),
+        })
+    }
+
+    // Draws all the numbers and returns the boards in the order they won, with their score.
+    // Boards sharing a winning number keep their input order
+    pub fn play(&mut self) -> &[(usize, usize)] {
+        while self.step() {}
+        &self.winners
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells = self.numbers.iter().zip(&self.marked);
+
+        for row in cells.collect::<Vec<_>>().chunks(self.width) {
+            for &(n, &marked) in row {
+                if marked {
+                    write!(f, "[{:>2}]", n)?;
+                } else {
+                    write!(f, " {:>2} ", n)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl Simulation for Bingo {
+    fn load(input: &str) -> Result<Self, String> {
+        Bingo::new(input, CLASSIC).map_err(|err| err.to_string())
+    }
+
+    // Boards stop being marked once they won
+    fn step(&mut self) -> bool {
+        let n = match self.numbers.get(self.drawn) {
+            Some(&n) => n,
+            None => return false,
+        };
+
+        for &(i, cell) in self.index.get(&n).into_iter().flatten() {
+            if !self.won[i] && self.boards[i].mark(cell, &self.rules) {
+                self.won[i] = true;
+                self.winners.push((i, self.boards[i].unmarked_sum * n));
+            }
+        }
+        self.drawn += 1;
+
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.drawn
+    }
+}
+
+impl fmt::Display for Bingo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let drawn = &self.numbers[..self.drawn];
+        writeln!(
+            f,
+            "Drawn {}/{}: {:?}",
+            self.drawn,
+            self.numbers.len(),
+            drawn
+        )?;
+
+        for (i, board) in self.boards.iter().enumerate() {
+            writeln!(f)?;
+            match self.winners.iter().position(|&(winner, _)| winner == i) {
+                Some(rank) => writeln!(
+                    f,
+                    "Board {} (winner #{}, score {})",
+                    i,
+                    rank + 1,
+                    self.winners[rank].1
+                )?,
+                None => writeln!(f, "Board {}", i)?,
+            }
+            write!(f, "{}", board)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn winners(input: &str, rules: &[Win]) -> Vec<(usize, usize)> {
+    Bingo::new(input, rules).unwrap().play().to_vec()
+}
+
+#[test]
+fn test_win_rules() {
+    let boards = "1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18
+
+1  2  3
+7  8  9
+10 11 12
+
+30 1 31
+32 4 33
+34 2 35
+
+40 5 41
+42 3 43
+6 44 9
+
+1 50 4
+51 3 52
+5 53 6
+
+1 60 61
+62 5 63
+64 65 9";
+
+    assert!(winners(boards, &[]).is_empty());
+    assert_eq!(winners(boards, &[Win::Rows]), [(0, 3 * 57)]);
+    assert_eq!(
+        winners(boards, &[Win::Columns]),
+        [(1, 4 * 195), (0, 10 * 23)]
+    );
+    assert_eq!(winners(boards, CLASSIC), [(0, 3 * 57), (1, 4 * 195)]);
+    assert_eq!(winners(boards, &[Win::Corners]), [(3, 6 * 206), (0, 0)]);
+    assert_eq!(
+        winners(boards, &[Win::Diagonals]),
+        [(3, 5 * 212), (4, 9 * 375), (0, 10 * 23)]
+    );
+    assert_eq!(winners(boards, &[Win::Full]), [(0, 0)]);
+}
+
+#[test]
+fn test_rectangular_boards() {
+    let boards = "5,1,2,6,3,4
+
+1 2 3 4
+5 6 7 8
+
+1 2
+3 4
+5 6";
+
+    assert_eq!(winners(boards, &[Win::Columns]), [(0, 30), (1, 3 * 4)]);
+    assert_eq!(winners(boards, &[Win::Rows]), [(1, 2 * 13), (0, 4 * 15)]);
+    assert_eq!(winners(boards, &[Win::Full]), [(1, 0)]);
+    // Neither board has diagonals
+    assert!(winners(boards, &[Win::Diagonals]).is_empty());
+}
+
+// Random boards of distinct numbers and a shuffled draw of every number, along with their input
+#[cfg(test)]
+fn generated(
+    seed: u64,
+    boards: usize,
+    numbers: usize,
+    (width, height): (usize, usize),
+) -> (String, Vec<usize>, Vec<Vec<usize>>) {
+    use crate::random::Lcg;
+
+    let mut lcg = Lcg::new(seed);
+    let mut random = |n: usize| lcg.below(n as u64) as usize;
+    let mut draws: Vec<usize> = (0..numbers).collect();
+    (1..draws.len())
+        .rev()
+        .for_each(|i| draws.swap(i, random(i + 1)));
+
+    let boards: Vec<Vec<usize>> = (0..boards)
+        .map(|_| {
+            let mut board = HashSet::new();
+            std::iter::repeat_with(|| random(numbers))
+                .filter(|&n| board.insert(n))
+                .take(width * height)
+                .collect()
+        })
+        .collect();
+
+    let mut input = draws
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    for board in &boards {
+        input.push('\n');
+        for row in board.chunks(width) {
+            let row: Vec<String> = row.iter().map(usize::to_string).collect();
+            input.push_str(&format!("\n{}", row.join(" ")));
+        }
+    }
+
+    (input, draws, boards)
+}
+
+// The engine before indexing, with the classic rules: every draw marks all the boards left,
+// each then counting its marked cells for every row and column
+#[cfg(test)]
+fn naive_winners(draws: &[usize], boards: &[Vec<usize>], width: usize) -> Vec<(usize, usize)> {
+    let height = boards[0].len() / width;
+    let cells: Vec<HashMap<usize, (usize, usize)>> = boards
+        .iter()
+        .map(|board| {
+            board
+                .iter()
+                .enumerate()
+                .map(|(i, &n)| (n, (i % width, i / width)))
+                .collect()
+        })
+        .collect();
+    let mut marked = vec![HashSet::new(); boards.len()];
+    let mut left: Vec<usize> = (0..boards.len()).collect();
+    let mut winners = Vec::new();
+
+    for &n in draws {
+        left.retain(|&i| {
+            let marked = &mut marked[i];
+            if let Some(&c) = cells[i].get(&n) {
+                marked.insert(c);
+            }
+
+            let won = (0..width)
+                .any(|x| marked.iter().filter(|(x1, _)| *x1 == x).count() == height)
+                || (0..height).any(|y| marked.iter().filter(|(_, y1)| *y1 == y).count() == width);
+            if won {
+                let unmarked = cells[i]
+                    .iter()
+                    .filter(|(_, c)| !marked.contains(c))
+                    .map(|(v, _)| v)
+                    .sum::<usize>();
+                winners.push((i, unmarked * n));
+            }
+
+            !won
+        });
+    }
+
+    winners
+}
+
+#[test]
+fn test_many_boards() {
+    // Thousands of random boards against a long draw, checked with the draw where each board
+    // completes its first row, column or corners, found without playing
+    let (width, height) = (6, 5);
+    let (input, draws, boards) = generated(4, 3000, 20_000, (width, height));
+
+    let mut position = vec![0; draws.len()];
+    draws.iter().enumerate().for_each(|(i, &n)| position[n] = i);
+    let mut expected: Vec<(usize, usize, usize)> = boards
+        .iter()
+        .enumerate()
+        .map(|(i, board)| {
+            let drawn_at = |cells: &mut dyn Iterator<Item = usize>| {
+                cells.map(|cell| position[board[cell]]).max().unwrap()
+            };
+            let rows = (0..height).map(|y| drawn_at(&mut (0..width).map(|x| y * width + x)));
+            let columns = (0..width).map(|x| drawn_at(&mut (0..height).map(|y| y * width + x)));
+            let corners =
+                drawn_at(&mut [0, width - 1, width * (height - 1), width * height - 1].into_iter());
+            let won_at = rows.chain(columns).chain([corners]).min().unwrap();
+
+            let unmarked = board
+                .iter()
+                .filter(|&&n| position[n] > won_at)
+                .sum::<usize>();
+            (won_at, i, unmarked * draws[won_at])
+        })
+        .collect();
+    expected.sort();
+
+    let mut bingo = Bingo::new(&input, &[Win::Rows, Win::Columns, Win::Corners]).unwrap();
+    let winners = bingo.play();
+
+    assert_eq!(
+        winners,
+        expected
+            .into_iter()
+            .map(|(_, i, score)| (i, score))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+#[ignore = "benchmark, run with `cargo test --release -- --ignored --nocapture`"]
+fn bench_many_boards() {
+    use std::time::Instant;
+
+    let (input, draws, boards) = generated(50, 5000, 10_000, (5, 5));
+    let mut bingo = Bingo::new(&input, CLASSIC).unwrap();
+
+    let now = Instant::now();
+    let indexed = bingo.play().to_vec();
+    let indexed_took = now.elapsed();
+
+    let now = Instant::now();
+    let naive = naive_winners(&draws, &boards, 5);
+    let naive_took = now.elapsed();
+
+    println!(
+        "{} boards, {} draws: indexed {:?}, naive {:?}",
+        boards.len(),
+        draws.len(),
+        indexed_took,
+        naive_took
+    );
+    assert_eq!(indexed, naive);
+    assert!(indexed_took < naive_took);
+}
+
+#[test]
+fn test_parse_errors() {
+    let invalid = |input: &str| Bingo::new(input, CLASSIC).unwrap_err().to_string();
+
+    assert_eq!(invalid(""), "Line 1: no numbers to draw");
+    assert_eq!(invalid("1,2,x"), "Line 1: invalid number 'x'");
+    assert_eq!(invalid("1,2\n"), "Line 1: no boards");
+    assert_eq!(
+        invalid("1,2\n\n1 2\n3 4 5"),
+        "Line 4: expected 2 numbers, found 3"
+    );
+    assert_eq!(
+        invalid("1,2\n\n1 2\n3 4\n\n1 2\n3 1"),
+        "Line 7: 1 is already on the board"
+    );
+    assert_eq!(invalid("1\n\n1 -2"), "Line 3: invalid number '-2'");
+}
+
+#[test]
+fn test_load() {
+    // The repl gets the message instead of a panic
+    assert_eq!(
+        <Bingo as Simulation>::load("1,2\n").unwrap_err(),
+        "Line 1: no boards"
+    );
+    assert!(<Bingo as Simulation>::load("1,2\n\n1 2\n3 4").is_ok());
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1(
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7"
+        ),
+        4512
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2(
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7"
+        ),
+        1924
+    );
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::image::Image;
+
 type Point = (isize, isize);
 #[derive(Debug)]
 struct Line {
@@ -28,17 +30,19 @@ fn parse(input: &str) -> Vec<Line> {
         .collect()
 }
 
-pub fn p1(input: &str) -> usize {
-    let lines = parse(input);
-
-    let map: HashMap<Point, usize> = lines
-        .iter()
-        .filter(|line| !line.is_diagonal())
+fn vents_map<'a>(lines: impl Iterator<Item = &'a Line>) -> HashMap<Point, usize> {
+    lines
         .flat_map(|line| line.iter_points())
         .fold(HashMap::new(), |mut acc, point| {
             *acc.entry(point).or_insert(0) += 1;
             acc
-        });
+        })
+}
+
+pub fn p1(input: &str) -> usize {
+    let lines = parse(input);
+
+    let map = vents_map(lines.iter().filter(|line| !line.is_diagonal()));
 
     map.values().filter(|v| **v >= 2).count()
 }
@@ -46,18 +50,33 @@ pub fn p1(input: &str) -> usize {
 pub fn p2(input: &str) -> usize {
     let lines = parse(input);
 
-    let map: HashMap<Point, usize> =
-        lines
-            .iter()
-            .flat_map(|line| line.iter_points())
-            .fold(HashMap::new(), |mut acc, point| {
-                *acc.entry(point).or_insert(0) += 1;
-                acc
-            });
+    let map = vents_map(lines.iter());
 
     map.values().filter(|v| **v >= 2).count()
 }
 
+pub fn image(input: &str) -> Image<u8> {
+    let lines = parse(input);
+    let map = vents_map(lines.iter());
+
+    let min_x = map.keys().map(|(x, _)| *x).min().unwrap();
+    let max_x = map.keys().map(|(x, _)| *x).max().unwrap();
+    let min_y = map.keys().map(|(_, y)| *y).min().unwrap();
+    let max_y = map.keys().map(|(_, y)| *y).max().unwrap();
+    let max_overlap = *map.values().max().unwrap();
+
+    let mut image = Image::new((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+    for (&(x, y), &count) in &map {
+        image.set(
+            (x - min_x) as usize,
+            (y - min_y) as usize,
+            (count * 255 / max_overlap) as u8,
+        );
+    }
+
+    image
+}
+
 impl Line {
     fn iter_points(&self) -> impl Iterator<Item = Point> + '_ {
         let x_diff = (self.p1.0 - self.p2.0).abs();
@@ -69,8 +88,8 @@ impl Line {
         let dist = std::cmp::max(x_diff, y_diff);
 
         (0..=dist).map(move |i| {
-            let x = self.p1.0 + (dx * i as isize);
-            let y = self.p1.1 + (dy * i as isize);
+            let x = self.p1.0 + (dx * i);
+            let y = self.p1.1 + (dy * i);
 
             (x, y)
         })
@@ -183,3 +202,27 @@ fn test_points() {
         ]
     );
 }
+
+#[test]
+fn test_image() {
+    let image = image(
+        "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2",
+    );
+
+    // One pixel per point, brighter with the number of overlapping vents (3 at most)
+    assert_eq!(image.size(), (10, 10));
+    assert_eq!(image.get(1, 0), 0);
+    assert_eq!(image.get(0, 0), 85);
+    assert_eq!(image.get(0, 9), 170);
+    assert_eq!(image.get(4, 4), 255);
+    assert_eq!(image.get(6, 4), 255);
+}
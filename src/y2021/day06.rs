@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::parsing;
+use crate::repl::Simulation;
+
+#[derive(Debug)]
+pub struct School {
+    fishes: VecDeque<usize>,
+    day: usize,
+}
+
+fn parse(input: &str) -> VecDeque<usize> {
+    parsing::separated(input, ',').fold(VecDeque::from([0; 9]), |mut v, x: usize| {
+        v[x] += 1;
+        v
+    })
+}
+fn simulation(data: &mut VecDeque<usize>, days: usize) {
+    for _ in 0..days {
+        data.rotate_left(1);
+        data[6] += data[8];
+    }
+}
+
+pub fn p1(input: &str) -> usize {
+    let mut fishes = parse(input);
+
+    simulation(&mut fishes, 80);
+
+    fishes.iter().sum()
+}
+
+pub fn p2(input: &str) -> usize {
+    let mut fishes = parse(input);
+
+    simulation(&mut fishes, 256);
+
+    fishes.iter().sum()
+}
+
+impl Simulation for School {
+    fn load(input: &str) -> Result<Self, String> {
+        Ok(School {
+            fishes: parse(input),
+            day: 0,
+        })
+    }
+
+    fn step(&mut self) -> bool {
+        simulation(&mut self.fishes, 1);
+        self.day += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.day
+    }
+}
+
+impl fmt::Display for School {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "After {} days: {} fishes",
+            self.day,
+            self.fishes.iter().sum::<usize>()
+        )?;
+
+        for (timer, count) in self.fishes.iter().enumerate() {
+            writeln!(f, "  timer {}: {}", timer, count)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(p1("3,4,3,1,2"), 5934);
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(p2("3,4,3,1,2"), 26984457539);
+}
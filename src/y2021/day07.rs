@@ -1,8 +1,10 @@
+use crate::parsing;
+
 fn parse(input: &str) -> Vec<usize> {
-    input.split(',').map(|n| n.parse().unwrap()).collect()
+    parsing::separated(input, ',').collect()
 }
 
-fn solve<F>(data: &Vec<usize>, f: F) -> usize
+fn solve<F>(data: &[usize], f: F) -> usize
 where
     F: Fn(usize, usize) -> usize,
 {
@@ -19,7 +21,7 @@ pub fn p1(input: &str) -> usize {
     let data = parse(input);
 
     solve(&data, |n: usize, desired_pos: usize| {
-        (n as isize - desired_pos as isize).abs() as usize
+        (n as isize - desired_pos as isize).unsigned_abs()
     })
 }
 
@@ -27,7 +29,7 @@ pub fn p2(input: &str) -> usize {
     let data = parse(input);
 
     solve(&data, |n: usize, desired_pos: usize| {
-        let steps = (n as isize - desired_pos as isize).abs() as usize;
+        let steps = (n as isize - desired_pos as isize).unsigned_abs();
         steps * (steps + 1) / 2
     })
 }
@@ -4,8 +4,7 @@ fn parse(input: &str) -> impl Iterator<Item = (&str, &str)> + '_ {
 
 pub fn p1(input: &str) -> usize {
     parse(input)
-        .map(|(_, digits)| digits.split_whitespace())
-        .flatten()
+        .flat_map(|(_, digits)| digits.split_whitespace())
         .filter(|d| [2, 4, 3, 7].contains(&d.len()))
         .count()
 }
@@ -21,19 +20,11 @@ pub fn p2(input: &str) -> usize {
         .sum()
 }
 
-impl<'a> Decoder<'_> {
-    fn new(patterns: &str) -> Decoder {
-        let one = patterns
-            .split_whitespace()
-            .filter(|s| s.len() == 2)
-            .next()
-            .unwrap();
+impl Decoder<'_> {
+    fn new(patterns: &str) -> Decoder<'_> {
+        let one = patterns.split_whitespace().find(|s| s.len() == 2).unwrap();
 
-        let four = patterns
-            .split_whitespace()
-            .filter(|s| s.len() == 4)
-            .next()
-            .unwrap();
+        let four = patterns.split_whitespace().find(|s| s.len() == 4).unwrap();
 
         Decoder { one, four }
     }
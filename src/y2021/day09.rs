@@ -1,54 +1,32 @@
 use std::collections::HashSet;
 
-type CavesMap = Vec<Vec<usize>>;
-type Coordinate = (usize, usize);
+use crate::grid::{Coordinate, Grid};
+use crate::image::{palette, Image, Rgb};
+
+type CavesMap = Grid<Height>;
 type Height = usize;
 
+const DELTAS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
 fn parse(input: &str) -> CavesMap {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
-                .collect()
-        })
-        .collect()
+    Grid::parse(input, |c| c.to_digit(10).unwrap() as usize)
 }
 
 fn iter_caves_with_coord(caves: &CavesMap) -> impl Iterator<Item = (Height, Coordinate)> + '_ {
-    caves
-        .iter()
-        .enumerate()
-        .flat_map(move |(y, cl)| cl.iter().enumerate().map(move |(x, h)| (*h, (x, y))))
+    caves.iter().map(|(coord, h)| (*h, coord))
 }
 
 fn get_neigh<'a>(
     caves: &'a CavesMap,
     coord: Coordinate,
 ) -> impl Iterator<Item = (Height, Coordinate)> + 'a {
-    [(0, 1), (1, 0), (0, -1), (-1, 0)]
-        .iter()
-        .filter_map(move |(dx, dy)| {
-            let x = coord.0 as isize + dx;
-            let y = coord.1 as isize + dy;
-
-            if y < 0 || y >= caves.len() as isize {
-                return None;
-            }
-
-            if x < 0 || x >= caves[0].len() as isize {
-                return None;
-            }
-
-            let x = x as usize;
-            let y = y as usize;
-
-            return Some((caves[y][x], (x, y)));
-        })
+    caves
+        .neighbours(coord, &DELTAS)
+        .map(move |neigh| (caves[neigh], neigh))
 }
 
 fn is_low(caves: &CavesMap, (height, coord): (Height, Coordinate)) -> bool {
-    get_neigh(&caves, coord)
+    get_neigh(caves, coord)
         .chain(std::iter::once((height, coord)))
         .map(|(height, _)| height)
         .min()
@@ -57,7 +35,7 @@ fn is_low(caves: &CavesMap, (height, coord): (Height, Coordinate)) -> bool {
 }
 
 fn low_points(caves: &CavesMap) -> impl Iterator<Item = (Height, Coordinate)> + '_ {
-    iter_caves_with_coord(&caves).filter(move |cave| is_low(&caves, *cave))
+    iter_caves_with_coord(caves).filter(move |cave| is_low(caves, *cave))
 }
 
 pub fn p1(input: &str) -> usize {
@@ -66,7 +44,7 @@ pub fn p1(input: &str) -> usize {
     low_points(&caves).map(|(h, _)| h + 1).sum()
 }
 
-fn find_basins<'a>(caves: &CavesMap) -> impl Iterator<Item = HashSet<Coordinate>> + '_ {
+fn find_basins(caves: &CavesMap) -> impl Iterator<Item = HashSet<Coordinate>> + '_ {
     fn compute_basin(caves: &CavesMap, current: (Height, Coordinate)) -> HashSet<Coordinate> {
         std::iter::once(current.1)
             .chain(
@@ -92,6 +70,32 @@ pub fn p2(input: &str) -> usize {
     basins.iter().rev().take(3).product()
 }
 
+pub fn image(input: &str) -> Image<Rgb> {
+    let caves = parse(input);
+
+    // Cells outside of any basin are shaded in grey by their height
+    let mut image = Image::new(caves.width(), caves.height());
+    for (h, (x, y)) in iter_caves_with_coord(&caves) {
+        let shade = (h * 255 / 9) as u8;
+        image.set(x, y, [shade / 4, shade / 4, shade / 4]);
+    }
+
+    // The deeper the cell, the brighter the basin colour
+    for (i, basin) in find_basins(&caves).enumerate() {
+        let color = palette(i);
+        for &(x, y) in &basin {
+            let depth = 10 - caves[(x, y)];
+            image.set(x, y, color.map(|c| (c as usize * depth / 10) as u8));
+        }
+    }
+
+    for (_, (x, y)) in low_points(&caves) {
+        image.set(x, y, [255, 255, 255]);
+    }
+
+    image.scaled(4)
+}
+
 #[test]
 fn test_p1() {
     assert_eq!(
@@ -134,3 +138,22 @@ fn test_neigh() {
 
     assert_eq!(neigh, [6, 6, 8, 8]);
 }
+
+#[test]
+fn test_image() {
+    let image = image(
+        "2199943210
+3987894921
+9856789892
+8767896789
+9899965678",
+    );
+
+    // Every cell is a 4x4 square, low points in white and the 9s outside basins in dark grey
+    assert_eq!(image.size(), (40, 20));
+    for (x, y) in [(1, 0), (9, 0), (2, 2), (6, 4)] {
+        assert_eq!(image.get(x * 4 + 3, y * 4 + 3), [255, 255, 255]);
+    }
+    assert_eq!(image.get(0, 8), [63, 63, 63]);
+    assert_ne!(image.get(0, 0), [63, 63, 63]);
+}
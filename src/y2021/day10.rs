@@ -1,3 +1,7 @@
+use std::io::BufRead;
+
+use crate::parsing;
+
 #[derive(Debug)]
 enum LineError {
     Incomplete(Vec<char>),
@@ -39,28 +43,33 @@ fn check_line(line: &str) -> Result<(), LineError> {
     Ok(())
 }
 
+fn parse(reader: impl BufRead) -> impl Iterator<Item = String> {
+    parsing::read_lines(reader)
+}
+
 pub fn p1(input: &str) -> usize {
-    input
-        .lines()
-        .map(check_line)
+    stream_p1(input.as_bytes())
+}
+
+pub fn stream_p1(reader: impl BufRead) -> usize {
+    parse(reader)
+        .map(|line| check_line(&line))
         .filter_map(|res| res.err())
-        .filter(|err| match err {
-            LineError::Corrupted(_) => true,
-            _ => false,
-        })
+        .filter(|err| matches!(err, LineError::Corrupted(_)))
         .map(|err| err.score())
         .sum()
 }
 
 pub fn p2(input: &str) -> usize {
-    let mut scores: Vec<usize> = input
-        .lines()
-        .map(check_line)
+    stream_p2(input.as_bytes())
+}
+
+// The median needs every score, but only the scores are kept and not the lines
+pub fn stream_p2(reader: impl BufRead) -> usize {
+    let mut scores: Vec<usize> = parse(reader)
+        .map(|line| check_line(&line))
         .filter_map(|res| res.err())
-        .filter(|err| match err {
-            LineError::Incomplete(_) => true,
-            _ => false,
-        })
+        .filter(|err| matches!(err, LineError::Incomplete(_)))
         .map(|err| err.score())
         .collect();
 
@@ -93,6 +102,31 @@ impl LineError {
     }
 }
 
+#[test]
+fn test_stream() {
+    // The example over and over: the syntax scores add up and the median stays the same
+    use crate::parsing::Generated;
+
+    let example = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+";
+    let log = || {
+        let bytes = example.bytes().cycle().take(example.len() * 1001);
+        std::io::BufReader::with_capacity(5, Generated(bytes))
+    };
+
+    assert_eq!(stream_p1(log()), 1001 * 26397);
+    assert_eq!(stream_p2(log()), 288957);
+}
+
 #[test]
 fn test_p1() {
     assert_eq!(
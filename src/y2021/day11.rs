@@ -0,0 +1,252 @@
+use std::fmt;
+use std::io;
+
+use crate::grid::ADJACENT;
+use crate::image::Image;
+use crate::render::{Color, Frame, Renderer};
+use crate::repl::Simulation;
+
+const N: usize = 10;
+const THRESHOLD: i8 = 10;
+type Octopuses = [i8; N * N];
+
+#[derive(Debug)]
+pub struct Cavern {
+    octopuses: Octopuses,
+    step: usize,
+    flashes: usize,
+}
+
+pub fn p1(input: &str) -> usize {
+    let octopuses = &mut parse(input);
+    (0..100).map(|_| next_step(octopuses)).sum()
+}
+
+pub fn p2(input: &str) -> usize {
+    let octopuses = &mut parse(input);
+    (1..).find(|_| next_step(octopuses) == N * N).unwrap()
+}
+
+fn parse(input: &str) -> Octopuses {
+    input
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .map(|c| c.to_digit(10).unwrap() as i8)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+fn next_step(octopuses: &mut Octopuses) -> usize {
+    octopuses.iter_mut().for_each(|x| match *x {
+        -1 => *x = 1,
+        _ => *x += 1,
+    });
+
+    (0..N * N)
+        .map(|pos| {
+            if octopuses[pos] >= THRESHOLD {
+                flash(octopuses, pos)
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+fn flash(octopuses: &mut Octopuses, pos: usize) -> usize {
+    octopuses[pos] = -1;
+    1 + ADJACENT
+        .into_iter()
+        .map(move |(dj, di)| {
+            (
+                ((pos / N) as isize + di) as usize,
+                ((pos % N) as isize + dj) as usize,
+            )
+        })
+        .filter(|&(ii, jj)| ii < N && jj < N)
+        .map(|(ii, jj)| ii * N + jj)
+        .filter_map(|pos| {
+            (octopuses[pos] != -1)
+                .then(|| octopuses[pos] += 1)
+                .and((octopuses[pos] >= THRESHOLD).then(|| flash(octopuses, pos)))
+        })
+        .sum::<usize>()
+}
+
+pub fn visualize(input: &str, renderer: &mut Renderer) -> io::Result<()> {
+    let mut cavern = Cavern::new(input);
+
+    loop {
+        renderer.draw(&cavern.frame())?;
+
+        if cavern.step > 0 && cavern.octopuses.iter().all(|&energy| energy == -1) {
+            return Ok(());
+        }
+        cavern.step();
+    }
+}
+
+pub fn image(input: &str, steps: usize) -> Image<u8> {
+    let mut cavern = Cavern::new(input);
+    (0..steps).for_each(|_| {
+        cavern.step();
+    });
+
+    // Octopuses which flashed during the last step are the brightest
+    let mut image = Image::new(N, N);
+    for (pos, &energy) in cavern.octopuses.iter().enumerate() {
+        let shade = match energy {
+            -1 => 255,
+            _ => energy as u8 * 20,
+        };
+        image.set(pos % N, pos / N, shade);
+    }
+
+    image.scaled(16)
+}
+
+impl Cavern {
+    fn new(input: &str) -> Self {
+        Cavern {
+            octopuses: parse(input),
+            step: 0,
+            flashes: 0,
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::new(
+            format!("Step {}: {} flashes", self.step, self.flashes),
+            N,
+            N,
+        );
+
+        for (pos, &energy) in self.octopuses.iter().enumerate() {
+            let (ch, color) = match energy {
+                -1 => ('0', Some(Color::Yellow)),
+                9 => ('9', Some(Color::Red)),
+                _ => (char::from(b'0' + energy as u8), Some(Color::Gray)),
+            };
+            frame.set(pos % N, pos / N, ch, color);
+        }
+
+        frame
+    }
+}
+
+impl Simulation for Cavern {
+    fn load(input: &str) -> Result<Self, String> {
+        Ok(Cavern::new(input))
+    }
+
+    fn step(&mut self) -> bool {
+        self.flashes += next_step(&mut self.octopuses);
+        self.step += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.step
+    }
+}
+
+impl fmt::Display for Cavern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "After step {}: {} flashes", self.step, self.flashes)?;
+
+        for row in self.octopuses.chunks(N) {
+            for &energy in row {
+                // Octopuses which flashed during the last step are stored as -1
+                write!(f, "{}", energy.max(0))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"),
+        1656
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"),
+        195
+    );
+}
+
+#[test]
+fn test_image() {
+    let input = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    // Every octopus is a 16x16 square, shaded by its energy until they all flash together
+    let start = image(input, 0);
+    assert_eq!(start.size(), (160, 160));
+    assert_eq!(start.get(0, 0), 100);
+    assert_eq!(start.get(159, 159), 120);
+
+    let synchronized = image(input, 195);
+    assert_eq!(synchronized.size(), (160, 160));
+    assert!((0..160).all(|y| (0..160).all(|x| synchronized.get(x, y) == 255)));
+}
+
+#[test]
+fn test_visualize() {
+    let frames = crate::render::record("day11", |renderer| {
+        visualize(
+            "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526",
+            renderer,
+        )
+    });
+
+    // The starting state, then every step until they all flash
+    assert_eq!(frames.len(), 196);
+    assert_eq!(
+        frames[195],
+        format!("Step 195: 3125 flashes\n{}", "0000000000\n".repeat(10))
+    );
+}
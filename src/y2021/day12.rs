@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 type Graph<'a> = HashMap<&'a str, Vec<&'a str>>;
 
-fn parse(input: &str) -> Graph {
+fn parse(input: &str) -> Graph<'_> {
     input
         .lines()
         .map(|l| l.split_once("-").unwrap())
@@ -0,0 +1,310 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+
+use crate::image::Image;
+use crate::render::{Color, Frame, Renderer};
+use crate::repl::Simulation;
+
+type Coordinate = (usize, usize);
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    FoldX(usize),
+    FoldY(usize),
+}
+
+#[derive(Debug, Default)]
+struct Origami {
+    dots: HashSet<Coordinate>,
+}
+
+#[derive(Debug)]
+pub struct Paper {
+    origami: Origami,
+    instructions: Vec<Instruction>,
+    folds: usize,
+}
+
+fn parse(input: &str) -> (Origami, impl Iterator<Item = Instruction> + '_) {
+    let (dots, instructions) = input.split_once("\n\n").unwrap();
+    (
+        Origami::new(
+            dots.lines()
+                .map(|l| {
+                    l.split_once(',')
+                        .map(|(x, y)| (x.parse().unwrap(), y.parse().unwrap()))
+                        .unwrap()
+                })
+                .collect(),
+        ),
+        instructions.lines().map(|l| {
+            let n = l.split('=').next_back().unwrap().parse().unwrap();
+            if l.contains('y') {
+                return Instruction::FoldY(n);
+            }
+            Instruction::FoldX(n)
+        }),
+    )
+}
+
+pub fn p1(input: &str) -> usize {
+    let (origami, instructions) = parse(input);
+
+    instructions.take(1).fold(origami, do_origami).dots.len()
+}
+
+pub fn p2(input: &str) {
+    let (origami, instructions) = parse(input);
+
+    let folded = instructions.fold(origami, do_origami);
+
+    print!("{}", folded);
+}
+
+pub fn image(input: &str) -> Image<u8> {
+    let (origami, instructions) = parse(input);
+
+    let folded = instructions.fold(origami, do_origami);
+
+    let mx = folded.dots.iter().map(|(x, _)| *x).max().unwrap_or(0);
+    let my = folded.dots.iter().map(|(_, y)| *y).max().unwrap_or(0);
+
+    let mut image = Image::new(mx + 1, my + 1);
+    for &(x, y) in &folded.dots {
+        image.set(x, y, 255);
+    }
+
+    image.scaled(8)
+}
+
+impl Origami {
+    fn new(dots: HashSet<Coordinate>) -> Self {
+        Self { dots }
+    }
+}
+
+impl fmt::Display for Origami {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mx = self.dots.iter().map(|(x, _)| *x).max().unwrap_or(0);
+        let my = self.dots.iter().map(|(_, y)| *y).max().unwrap_or(0);
+
+        for y in 0..=my {
+            for x in 0..=mx {
+                if self.dots.contains(&(x, y)) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn visualize(input: &str, renderer: &mut Renderer) -> io::Result<()> {
+    let mut paper = Paper::new(input);
+
+    loop {
+        renderer.draw(&paper.frame())?;
+
+        if !paper.step() {
+            return Ok(());
+        }
+    }
+}
+
+impl Paper {
+    fn new(input: &str) -> Self {
+        let (origami, instructions) = parse(input);
+
+        Paper {
+            origami,
+            instructions: instructions.collect(),
+            folds: 0,
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        let mx = self.origami.dots.iter().map(|(x, _)| *x).max().unwrap_or(0);
+        let my = self.origami.dots.iter().map(|(_, y)| *y).max().unwrap_or(0);
+
+        let mut frame = Frame::new(
+            format!(
+                "After {}/{} folds: {} dots",
+                self.folds,
+                self.instructions.len(),
+                self.origami.dots.len()
+            ),
+            mx + 1,
+            my + 1,
+        );
+
+        for y in 0..=my {
+            for x in 0..=mx {
+                frame.set(x, y, '.', Some(Color::Gray));
+            }
+        }
+
+        for &(x, y) in &self.origami.dots {
+            frame.set(x, y, '#', Some(Color::Cyan));
+        }
+
+        frame
+    }
+}
+
+impl Simulation for Paper {
+    fn load(input: &str) -> Result<Self, String> {
+        Ok(Paper::new(input))
+    }
+
+    fn step(&mut self) -> bool {
+        match self.instructions.get(self.folds) {
+            Some(&instruction) => {
+                self.origami = do_origami(std::mem::take(&mut self.origami), instruction);
+                self.folds += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn steps(&self) -> usize {
+        self.folds
+    }
+}
+
+impl fmt::Display for Paper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "After {}/{} folds: {} dots, next fold: {:?}",
+            self.folds,
+            self.instructions.len(),
+            self.origami.dots.len(),
+            self.instructions.get(self.folds)
+        )?;
+        write!(f, "{}", self.origami)
+    }
+}
+
+fn do_origami(mut origami: Origami, instruction: Instruction) -> Origami {
+    origami.dots = origami
+        .dots
+        .iter()
+        .map(|(x, y)| match instruction {
+            Instruction::FoldX(n) => (n - x.abs_diff(n), *y),
+            Instruction::FoldY(n) => (*x, n - y.abs_diff(n)),
+        })
+        .collect();
+
+    origami
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5"),
+        17
+    );
+}
+
+#[test]
+fn test_image() {
+    let image = image(
+        "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5",
+    );
+
+    // The folded example is the outline of a 5x5 square, each dot an 8x8 square
+    assert_eq!(image.size(), (40, 40));
+    assert_eq!(image.get(0, 0), 255);
+    assert_eq!(image.get(39, 39), 255);
+    assert_eq!(image.get(16, 0), 255);
+    assert_eq!(image.get(20, 20), 0);
+}
+
+#[test]
+fn test_visualize() {
+    let frames = crate::render::record("day13", |renderer| {
+        visualize(
+            "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5",
+            renderer,
+        )
+    });
+
+    // The unfolded paper, then one frame per fold
+    assert_eq!(frames.len(), 3);
+    assert_eq!(
+        frames[2],
+        "After 2/2 folds: 16 dots
+#####
+#...#
+#...#
+#...#
+#####
+"
+    );
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::repl::Simulation;
+
+type PairInsertions = BTreeMap<(char, char), char>;
+
+fn parse(input: &str) -> (String, PairInsertions) {
+    let (polymer_template, pair_insertions) = input.split_once("\n\n").unwrap();
+
+    (
+        polymer_template.to_string(),
+        pair_insertions
+            .lines()
+            .map(|l| {
+                let (pair, insertion) = l.split_once(" -> ").unwrap();
+                let mut pair_chars = pair.chars();
+                let first = pair_chars.next().unwrap();
+                let second = pair_chars.next().unwrap();
+
+                ((first, second), insertion.chars().next().unwrap())
+            })
+            .collect(),
+    )
+}
+
+#[derive(Debug)]
+pub struct Polymer {
+    pair_insertions: PairInsertions,
+    pairs_count: BTreeMap<(char, char), usize>,
+    letters_count: BTreeMap<char, usize>,
+    step: usize,
+}
+
+impl Polymer {
+    fn new(template: &str, pair_insertions: PairInsertions) -> Self {
+        let pairs_count = template
+            .as_bytes()
+            .windows(2)
+            .fold(BTreeMap::new(), |mut acc, w| {
+                *acc.entry((w[0] as char, w[1] as char)).or_default() += 1;
+                acc
+            });
+
+        let letters_count = template.chars().fold(BTreeMap::new(), |mut acc, c| {
+            *acc.entry(c).or_default() += 1;
+            acc
+        });
+
+        Polymer {
+            pair_insertions,
+            pairs_count,
+            letters_count,
+            step: 0,
+        }
+    }
+
+    fn next_step(&mut self) {
+        let mut new_pairs_count = BTreeMap::new();
+
+        for (&(first, second), &count) in &self.pairs_count {
+            let inter = self.pair_insertions[&(first, second)];
+
+            *new_pairs_count.entry((first, inter)).or_default() += count;
+            *new_pairs_count.entry((inter, second)).or_default() += count;
+
+            *self.letters_count.entry(inter).or_default() += count;
+        }
+
+        self.pairs_count = new_pairs_count;
+        self.step += 1;
+    }
+
+    fn score(&self) -> usize {
+        let max = *self.letters_count.values().max().unwrap();
+        let min = *self.letters_count.values().min().unwrap();
+
+        max - min
+    }
+}
+
+fn solve(template: &str, pair_insertions: PairInsertions, steps: usize) -> usize {
+    let mut polymer = Polymer::new(template, pair_insertions);
+
+    for _ in 0..steps {
+        polymer.next_step();
+    }
+
+    polymer.score()
+}
+
+pub fn p1(input: &str) -> usize {
+    let (template, pair_insertions) = parse(input);
+    solve(&template, pair_insertions, 10)
+}
+
+pub fn p2(input: &str) -> usize {
+    let (template, pair_insertions) = parse(input);
+    solve(&template, pair_insertions, 40)
+}
+
+impl Simulation for Polymer {
+    fn load(input: &str) -> Result<Self, String> {
+        let (template, pair_insertions) = parse(input);
+
+        Ok(Polymer::new(&template, pair_insertions))
+    }
+
+    fn step(&mut self) -> bool {
+        self.next_step();
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.step
+    }
+}
+
+impl fmt::Display for Polymer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "After step {}: length {}, score {}",
+            self.step,
+            self.letters_count.values().sum::<usize>(),
+            self.score()
+        )?;
+
+        for (letter, count) in &self.letters_count {
+            writeln!(f, "  {}: {}", letter, count)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"),
+        1588
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"),
+        2188189693529
+    );
+}
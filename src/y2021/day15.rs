@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use crate::grid::ORTHOGONAL;
+use crate::image::{Image, Rgb};
+use crate::render::{Color, Frame, Renderer};
+use crate::search::{search, Path};
+
+type Coordinate = (usize, usize);
+type CaveMap = HashMap<Coordinate, usize>;
+
+fn parse(input: &str) -> CaveMap {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, l)| {
+            l.chars()
+                .enumerate()
+                .map(move |(x, c)| ((x, y), c.to_digit(10).unwrap() as usize))
+        })
+        .collect()
+}
+
+fn neighs<'a>(
+    map: &'a CaveMap,
+    (x, y): Coordinate,
+) -> impl Iterator<Item = (Coordinate, usize)> + 'a {
+    ORTHOGONAL.iter().filter_map(move |(dx, dy)| {
+        let nx = (x as isize + dx) as usize;
+        let ny = (y as isize + dy) as usize;
+
+        let ncoord = (nx, ny);
+        if let Some(ncost) = map.get(&ncoord) {
+            return Some(((nx, ny), *ncost));
+        }
+
+        None
+    })
+}
+
+fn fewer_risky_path<F>(
+    map: &CaveMap,
+    start: Coordinate,
+    goal: Coordinate,
+    visit: F,
+) -> Option<Path<Coordinate>>
+where
+    F: FnMut(&Coordinate, &HashMap<Coordinate, usize>),
+{
+    // Dijkstra works faster for this particular puzzle's path because we have to go diagonally
+    // but we can move only vertically and horizontally so we can't have a heuristic which can justify
+    // the additional cost of A*
+    search(
+        start,
+        |&pos| pos == goal,
+        |&pos| neighs(map, pos),
+        |_| 0,
+        visit,
+    )
+}
+
+pub fn p1(input: &str) -> usize {
+    let map = parse(input);
+
+    let mx = *map.keys().map(|(x, _)| x).max().unwrap();
+    let my = *map.keys().map(|(_, y)| y).max().unwrap();
+
+    fewer_risky_path(&map, (0, 0), (mx, my), |_, _| ())
+        .unwrap()
+        .0
+}
+
+fn expand(map: &mut CaveMap, tiles: usize) {
+    let mx = *map.keys().map(|(x, _)| x).max().unwrap();
+    let my = *map.keys().map(|(_, y)| y).max().unwrap();
+
+    for (x, y) in (0..=my).flat_map(|y| (0..=mx).map(move |x| (x, y))) {
+        for (tx, ty) in (0..tiles).flat_map(|y| (0..tiles).map(move |x| (x, y))) {
+            let mut new_cost = map[&(x, y)] + (tx + ty);
+            if new_cost > 9 {
+                new_cost -= 9;
+            }
+            map.insert((x + tx * (mx + 1), y + ty * (my + 1)), new_cost);
+        }
+    }
+}
+
+pub fn p2(input: &str) -> usize {
+    let mut map = parse(input);
+
+    expand(&mut map, 5);
+
+    let mx = *map.keys().map(|(x, _)| x).max().unwrap();
+    let my = *map.keys().map(|(_, y)| y).max().unwrap();
+
+    fewer_risky_path(&map, (0, 0), (mx, my), |_, _| ())
+        .unwrap()
+        .0
+}
+
+pub fn image(input: &str, tiles: usize) -> Image<Rgb> {
+    let mut map = parse(input);
+
+    expand(&mut map, tiles);
+
+    let mx = *map.keys().map(|(x, _)| x).max().unwrap();
+    let my = *map.keys().map(|(_, y)| y).max().unwrap();
+
+    // The lower the risk, the brighter the cell
+    let mut image = Image::new(mx + 1, my + 1);
+    for (&(x, y), &risk) in &map {
+        let shade = ((10 - risk) * 25) as u8;
+        image.set(x, y, [shade, shade, shade]);
+    }
+
+    let (_, path) = fewer_risky_path(&map, (0, 0), (mx, my), |_, _| ()).unwrap();
+    for (x, y) in path {
+        image.set(x, y, [255, 0, 0]);
+    }
+
+    image.scaled(4)
+}
+
+pub fn visualize(input: &str, renderer: &mut Renderer) -> io::Result<()> {
+    let map = parse(input);
+
+    let mx = *map.keys().map(|(x, _)| x).max().unwrap();
+    let my = *map.keys().map(|(_, y)| y).max().unwrap();
+
+    // Draw roughly 200 frames whatever the size of the map
+    let every = (map.len() / 200).max(1);
+    let mut settled = HashSet::new();
+    let mut result = Ok(());
+
+    let frame = |title: String, settled: &HashSet<Coordinate>, dist: &HashMap<_, _>| {
+        let mut frame = Frame::new(title, mx + 1, my + 1);
+
+        for (&(x, y), &risk) in &map {
+            let color = if settled.contains(&(x, y)) {
+                Color::Blue
+            } else if dist.contains_key(&(x, y)) {
+                Color::Yellow
+            } else {
+                Color::Gray
+            };
+            frame.set(
+                x,
+                y,
+                char::from_digit(risk as u32, 10).unwrap(),
+                Some(color),
+            );
+        }
+
+        frame
+    };
+
+    let risk = fewer_risky_path(&map, (0, 0), (mx, my), |&pos, dist| {
+        if settled.insert(pos) && settled.len() % every == 0 && result.is_ok() {
+            let mut frame = frame(
+                format!("Visited {} positions", settled.len()),
+                &settled,
+                dist,
+            );
+            frame.set(pos.0, pos.1, '@', Some(Color::Red));
+            result = renderer.draw(&frame);
+        }
+    });
+    result?;
+
+    renderer.draw(&frame(
+        format!("Goal reached with a total risk of {}", risk.unwrap().0),
+        &settled,
+        &HashMap::new(),
+    ))
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"),
+        40
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"),
+        315
+    );
+}
+
+#[test]
+fn test_image() {
+    let input = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    // The path is drawn in red from the top left to the bottom right corner
+    let single = image(input, 1);
+    assert_eq!(single.size(), (40, 40));
+    assert_eq!(single.get(0, 0), [255, 0, 0]);
+    assert_eq!(single.get(39, 39), [255, 0, 0]);
+    assert_eq!(single.get(39, 0), [200, 200, 200]);
+
+    let full = image(input, 5);
+    assert_eq!(full.size(), (200, 200));
+    assert_eq!(full.get(0, 0), [255, 0, 0]);
+    assert_eq!(full.get(199, 199), [255, 0, 0]);
+}
+
+#[test]
+fn test_visualize() {
+    let frames = crate::render::record("day15", |renderer| {
+        visualize(
+            "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581",
+            renderer,
+        )
+    });
+
+    // One frame per visited position, the goal last, then the whole map once it is reached
+    assert_eq!(frames.len(), 98);
+    assert!(frames[96].starts_with("Visited 97 positions\n"));
+    assert!(frames[96].ends_with("\n231194458@\n"));
+    assert!(frames[97].starts_with("Goal reached with a total risk of 40\n"));
+    assert!(frames[97].ends_with("\n2311944581\n"));
+}
@@ -76,7 +76,7 @@ fn parse_operator(bits: &mut Bits) -> Payload {
             let mut sub_packets = Vec::new();
 
             let mut subpacket_bits = bits.take(length).peekable();
-            while let Some(_) = subpacket_bits.peek() {
+            while subpacket_bits.peek().is_some() {
                 sub_packets.push(Packet::parse(&mut subpacket_bits));
             }
 
@@ -103,13 +103,11 @@ impl Packet {
             _ => parse_operator(bits),
         };
 
-        let packet = Packet {
+        Packet {
             version,
             typ,
             payload,
-        };
-
-        packet
+        }
     }
 }
 
@@ -1,5 +1,12 @@
+use std::io;
+
+use crate::render::{Color, Frame, Renderer};
+
 type Coordinate = (i32, i32);
 
+const VIEW_WIDTH: i32 = 100;
+const VIEW_HEIGHT: i32 = 50;
+
 fn parse(input: &str) -> TargetArea {
     let (_, raw_coord) = input.split_once(": x=").unwrap();
 
@@ -54,6 +61,58 @@ pub fn p2(input: &str) -> usize {
     find_good_shots(&target).count()
 }
 
+pub fn visualize(input: &str, renderer: &mut Renderer) -> io::Result<()> {
+    let target = parse(input);
+    let best = find_good_shots(&target)
+        .max_by_key(|shot| shot.highest_y)
+        .unwrap();
+
+    // Scale the world down so the whole trajectory fits in the view
+    let (left, right) = (0.min(target.tl.0), target.br.0);
+    let (top, bottom) = (best.highest_y, target.br.1);
+    let sx = ((right - left + 1) + VIEW_WIDTH - 1) / VIEW_WIDTH;
+    let sy = ((top - bottom + 1) + VIEW_HEIGHT - 1) / VIEW_HEIGHT;
+    let to_view = |(x, y): Coordinate| (((x - left) / sx) as usize, ((top - y) / sy) as usize);
+
+    let mut shot = Shot::new((0, 0), best.init_velocity);
+    let mut trajectory = vec![shot.pos];
+
+    loop {
+        let mut frame = Frame::new(
+            format!(
+                "Velocity {:?}, position {:?}, highest y {} (1 cell = {}x{})",
+                shot.init_velocity, shot.pos, shot.highest_y, sx, sy
+            ),
+            ((right - left) / sx + 1) as usize,
+            ((top - bottom) / sy + 1) as usize,
+        );
+
+        for x in (target.tl.0..=target.br.0).step_by(sx as usize) {
+            for y in (target.br.1..=target.tl.1).step_by(sy as usize) {
+                let (vx, vy) = to_view((x, y));
+                frame.set(vx, vy, 'T', Some(Color::Green));
+            }
+        }
+        for &pos in &trajectory {
+            let (vx, vy) = to_view(pos);
+            frame.set(vx, vy, '#', Some(Color::Yellow));
+        }
+        let (vx, vy) = to_view((0, 0));
+        frame.set(vx, vy, 'S', Some(Color::Cyan));
+        let (vx, vy) = to_view(shot.pos);
+        frame.set(vx, vy, '@', Some(Color::Red));
+
+        renderer.draw(&frame)?;
+
+        if shot.has_reached_ta(&target) || shot.pos.1 < target.br.1 {
+            return Ok(());
+        }
+
+        shot.step();
+        trajectory.push(shot.pos);
+    }
+}
+
 #[derive(Debug)]
 struct TargetArea {
     tl: Coordinate,
@@ -111,3 +170,15 @@ fn test_p1_1() {
 fn test_p2() {
     assert_eq!(p2("target area: x=20..30, y=-10..-5"), 112);
 }
+
+#[test]
+fn test_visualize() {
+    let frames = crate::render::record("day17", |renderer| {
+        visualize("target area: x=20..30, y=-10..-5", renderer)
+    });
+
+    // One frame per step of the highest shot, until it lands in the target
+    assert_eq!(frames.len(), 21);
+    assert!(frames[20].starts_with("Velocity (7, 9), position (28, -10), highest y 45"));
+    assert!(frames[20].ends_with("TTTTTTTT@TT\n"));
+}
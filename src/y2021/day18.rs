@@ -0,0 +1,308 @@
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+fn parse(input: &str) -> Vec<Number> {
+    input.lines().map(|l| l.parse().unwrap()).collect()
+}
+
+pub fn p1(input: &str) -> u32 {
+    parse(input)
+        .into_iter()
+        .reduce(|acc, n| acc + n)
+        .unwrap()
+        .magnitude()
+}
+
+pub fn p2(input: &str) -> u32 {
+    let numbers = parse(input);
+
+    (0..numbers.len())
+        .flat_map(|i| (0..numbers.len()).map(move |j| (i, j)))
+        .filter(|(i, j)| i != j)
+        .map(|(i, j)| (numbers[i].clone() + numbers[j].clone()).magnitude())
+        .max()
+        .unwrap()
+}
+
+impl Number {
+    fn pair(left: Number, right: Number) -> Self {
+        Number::Pair(Box::new(left), Box::new(right))
+    }
+
+    fn magnitude(&self) -> u32 {
+        match self {
+            Number::Regular(n) => *n,
+            Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    fn reduce(&mut self) {
+        while self.explode(0).is_some() || self.split() {}
+    }
+
+    // Returns the values to add to the regular numbers on the left and on the right
+    fn explode(&mut self, depth: usize) -> Option<(u32, u32)> {
+        match self {
+            Number::Regular(_) => None,
+            Number::Pair(left, right) => {
+                if depth >= 4 {
+                    if let (Number::Regular(l), Number::Regular(r)) = (&**left, &**right) {
+                        let exploded = (*l, *r);
+                        *self = Number::Regular(0);
+                        return Some(exploded);
+                    }
+                }
+
+                if let Some((l, r)) = left.explode(depth + 1) {
+                    right.add_leftmost(r);
+                    return Some((l, 0));
+                }
+
+                if let Some((l, r)) = right.explode(depth + 1) {
+                    left.add_rightmost(l);
+                    return Some((0, r));
+                }
+
+                None
+            }
+        }
+    }
+
+    fn split(&mut self) -> bool {
+        match self {
+            Number::Regular(n) if *n >= 10 => {
+                *self = Number::pair(Number::Regular(*n / 2), Number::Regular(n.div_ceil(2)));
+                true
+            }
+            Number::Regular(_) => false,
+            Number::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(left, _) => left.add_leftmost(value),
+        }
+    }
+
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(_, right) => right.add_rightmost(value),
+        }
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    fn add(self, other: Number) -> Number {
+        let mut sum = Number::pair(self, other);
+        sum.reduce();
+        sum
+    }
+}
+
+fn parse_number(s: &[u8]) -> Result<(Number, &[u8]), ()> {
+    match s.split_first() {
+        Some((b'[', rest)) => {
+            let (left, rest) = parse_number(rest)?;
+            let rest = rest.strip_prefix(b",").ok_or(())?;
+            let (right, rest) = parse_number(rest)?;
+            let rest = rest.strip_prefix(b"]").ok_or(())?;
+
+            Ok((Number::pair(left, right), rest))
+        }
+        Some((c, _)) if c.is_ascii_digit() => {
+            let len = s.iter().take_while(|c| c.is_ascii_digit()).count();
+            let n = std::str::from_utf8(&s[..len]).unwrap().parse().unwrap();
+
+            Ok((Number::Regular(n), &s[len..]))
+        }
+        _ => Err(()),
+    }
+}
+
+impl FromStr for Number {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_number(s.trim().as_bytes())? {
+            (number, []) => Ok(number),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Regular(n) => write!(f, "{}", n),
+            Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+#[cfg(test)]
+fn n(s: &str) -> Number {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_parse_display() {
+    for s in [
+        "[1,2]",
+        "[[1,2],3]",
+        "[9,[8,7]]",
+        "[[1,9],[8,5]]",
+        "[[[[1,2],[3,4]],[[5,6],[7,8]]],9]",
+        "[[[9,[3,8]],[[0,9],6]],[[[3,7],[4,9]],3]]",
+        "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]",
+    ] {
+        assert_eq!(n(s).to_string(), s);
+    }
+
+    assert_eq!("[1,2".parse::<Number>(), Err(()));
+    assert_eq!("[1,2]]".parse::<Number>(), Err(()));
+}
+
+#[test]
+fn test_explode() {
+    for (before, after) in [
+        ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+        ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+        ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+        (
+            "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+            "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+        ),
+        (
+            "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+        ),
+    ] {
+        let mut number = n(before);
+        assert!(number.explode(0).is_some());
+        assert_eq!(number, n(after));
+    }
+}
+
+#[test]
+fn test_reduction_steps() {
+    let mut number = Number::pair(n("[[[[4,3],4],4],[7,[[8,4],9]]]"), n("[1,1]"));
+    assert_eq!(number, n("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]"));
+
+    assert!(number.explode(0).is_some());
+    assert_eq!(number, n("[[[[0,7],4],[7,[[8,4],9]]],[1,1]]"));
+
+    assert!(number.explode(0).is_some());
+    assert_eq!(number, n("[[[[0,7],4],[15,[0,13]]],[1,1]]"));
+
+    assert!(number.explode(0).is_none());
+    assert!(number.split());
+    assert_eq!(number, n("[[[[0,7],4],[[7,8],[0,13]]],[1,1]]"));
+
+    assert!(number.explode(0).is_none());
+    assert!(number.split());
+    assert_eq!(number, n("[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]"));
+
+    assert!(number.explode(0).is_some());
+    assert_eq!(number, n("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"));
+
+    assert!(number.explode(0).is_none());
+    assert!(!number.split());
+}
+
+#[test]
+fn test_sums() {
+    let sum = |input: &str| parse(input).into_iter().reduce(|acc, n| acc + n).unwrap();
+
+    assert_eq!(
+        n("[[[[4,3],4],4],[7,[[8,4],9]]]") + n("[1,1]"),
+        n("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+    );
+    assert_eq!(
+        sum("[1,1]\n[2,2]\n[3,3]\n[4,4]"),
+        n("[[[[1,1],[2,2]],[3,3]],[4,4]]")
+    );
+    assert_eq!(
+        sum("[1,1]\n[2,2]\n[3,3]\n[4,4]\n[5,5]"),
+        n("[[[[3,0],[5,3]],[4,4]],[5,5]]")
+    );
+    assert_eq!(
+        sum("[1,1]\n[2,2]\n[3,3]\n[4,4]\n[5,5]\n[6,6]"),
+        n("[[[[5,0],[7,4]],[5,5]],[6,6]]")
+    );
+    assert_eq!(
+        sum("[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]"),
+        n("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
+    );
+}
+
+#[test]
+fn test_magnitude() {
+    for (s, magnitude) in [
+        ("[[1,2],[[3,4],5]]", 143),
+        ("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384),
+        ("[[[[1,1],[2,2]],[3,3]],[4,4]]", 445),
+        ("[[[[3,0],[5,3]],[4,4]],[5,5]]", 791),
+        ("[[[[5,0],[7,4]],[5,5]],[6,6]]", 1137),
+        (
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+            3488,
+        ),
+    ] {
+        assert_eq!(n(s).magnitude(), magnitude);
+    }
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"),
+        4140
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"),
+        3993
+    );
+}
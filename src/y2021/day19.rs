@@ -0,0 +1,351 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::geometry::{Point3, Rotation};
+
+// Overlapping scanners share at least 12 beacons, so at least 12 * 11 / 2 distances
+const MIN_OVERLAP: usize = 12;
+const MIN_SHARED_DISTANCES: usize = MIN_OVERLAP * (MIN_OVERLAP - 1) / 2;
+
+#[derive(Debug)]
+struct Scanner {
+    beacons: Vec<Point3>,
+    fingerprint: HashMap<i64, usize>,
+}
+
+#[derive(Debug)]
+struct Located {
+    position: Point3,
+    // Beacons in the coordinate system of scanner 0
+    beacons: Vec<Point3>,
+}
+
+fn parse(input: &str) -> Vec<Scanner> {
+    input
+        .split("\n\n")
+        .map(|block| Scanner::new(block.lines().skip(1).map(|l| l.parse().unwrap()).collect()))
+        .collect()
+}
+
+pub fn p1(input: &str) -> usize {
+    assemble(&parse(input))
+        .iter()
+        .flat_map(|scanner| scanner.beacons.iter())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+pub fn p2(input: &str) -> i32 {
+    let located = assemble(&parse(input));
+
+    located
+        .iter()
+        .flat_map(|a| located.iter().map(|b| a.position.manhattan(&b.position)))
+        .max()
+        .unwrap()
+}
+
+impl Scanner {
+    fn new(beacons: Vec<Point3>) -> Self {
+        let mut fingerprint = HashMap::new();
+        for (i, a) in beacons.iter().enumerate() {
+            for b in &beacons[i + 1..] {
+                *fingerprint.entry(a.squared_distance(b)).or_default() += 1;
+            }
+        }
+
+        Self {
+            beacons,
+            fingerprint,
+        }
+    }
+
+    fn shared_distances(&self, other: &Scanner) -> usize {
+        self.fingerprint
+            .iter()
+            .filter_map(|(d, n)| other.fingerprint.get(d).map(|m| *n.min(m)))
+            .sum()
+    }
+
+    // Finds the orientation and position of the scanner relative to an already located one
+    fn locate(&self, reference: &Located, rotations: &[Rotation]) -> Option<Located> {
+        rotations.iter().find_map(|rotation| {
+            let rotated: Vec<Point3> = self.beacons.iter().map(|&b| rotation.apply(b)).collect();
+
+            let mut offsets: HashMap<Point3, usize> = HashMap::new();
+            for known in &reference.beacons {
+                for &beacon in &rotated {
+                    *offsets.entry(*known - beacon).or_default() += 1;
+                }
+            }
+
+            offsets
+                .into_iter()
+                .find(|(_, count)| *count >= MIN_OVERLAP)
+                .map(|(position, _)| Located {
+                    position,
+                    beacons: rotated.iter().map(|&b| b + position).collect(),
+                })
+        })
+    }
+}
+
+// Places every scanner in the coordinate system of scanner 0
+fn assemble(scanners: &[Scanner]) -> Vec<Located> {
+    let rotations = Rotation::all();
+
+    let mut located: Vec<Option<Located>> = scanners.iter().map(|_| None).collect();
+    located[0] = Some(Located {
+        position: Point3::ORIGIN,
+        beacons: scanners[0].beacons.clone(),
+    });
+
+    let mut queue = vec![0];
+    while let Some(reference) = queue.pop() {
+        for (i, scanner) in scanners.iter().enumerate() {
+            if located[i].is_some()
+                || scanner.shared_distances(&scanners[reference]) < MIN_SHARED_DISTANCES
+            {
+                continue;
+            }
+
+            let found = scanner.locate(located[reference].as_ref().unwrap(), &rotations);
+            if found.is_some() {
+                located[i] = found;
+                queue.push(i);
+            }
+        }
+    }
+
+    located
+        .into_iter()
+        .map(|l| l.expect("Scanner without enough overlapping beacons"))
+        .collect()
+}
+
+#[cfg(test)]
+fn synthetic_input() -> (String, usize, i32) {
+    // Deterministic pseudo-random beacons, seen by scanners at known positions and orientations
+    use crate::random::Lcg;
+
+    let mut lcg = Lcg::new(2021);
+    let mut random = |range: i32| lcg.below(2 * range as u64 + 1) as i32 - range;
+
+    let beacons: Vec<Point3> = (0..400)
+        .map(|_| Point3::new(random(1800), random(1800), random(1800)))
+        .collect();
+    let positions = [
+        Point3::new(0, 0, 0),
+        Point3::new(1105, -68, 29),
+        Point3::new(-92, 1130, -47),
+        Point3::new(1080, 1160, 105),
+    ];
+    let rotations = Rotation::all();
+
+    let mut input = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, &position) in positions.iter().enumerate() {
+        let rotation = rotations[(i * 7) % rotations.len()].inverse();
+        let visible = beacons
+            .iter()
+            .map(|&b| b - position)
+            .filter(|d| d.x.abs() <= 1000 && d.y.abs() <= 1000 && d.z.abs() <= 1000);
+
+        let mut block = format!("--- scanner {} ---", i);
+        for relative in visible {
+            seen.insert(relative + position);
+            block.push_str(&format!("\n{}", rotation.apply(relative)));
+        }
+        input.push(block);
+    }
+
+    let max_distance = positions
+        .iter()
+        .flat_map(|a| positions.iter().map(move |b| a.manhattan(b)))
+        .max()
+        .unwrap();
+
+    (input.join("\n\n"), seen.len(), max_distance)
+}
+
+#[test]
+fn test_orientations() {
+    let original = Scanner::new(
+        "-1,-1,1 -2,-2,2 -3,-3,3 -2,-3,1 5,6,-4 8,0,7"
+            .split(' ')
+            .map(|p| p.parse().unwrap())
+            .collect(),
+    );
+    let turned = Scanner::new(
+        "1,-1,1 2,-2,2 3,-3,3 2,-1,3 -5,4,-6 -8,-7,0"
+            .split(' ')
+            .map(|p| p.parse().unwrap())
+            .collect(),
+    );
+
+    assert_eq!(original.shared_distances(&turned), 15);
+    assert!(Rotation::all().iter().any(|rotation| {
+        original
+            .beacons
+            .iter()
+            .zip(&turned.beacons)
+            .all(|(&a, &b)| rotation.apply(b) == a)
+    }));
+}
+
+#[test]
+fn test_p1() {
+    let (input, beacons, _) = synthetic_input();
+
+    assert_eq!(p1(&input), beacons);
+}
+
+#[test]
+fn test_p2() {
+    let (input, _, max_distance) = synthetic_input();
+
+    assert_eq!(p2(&input), max_distance);
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+#[test]
+fn test_example() {
+    assert_eq!(p1(EXAMPLE), 79);
+    assert_eq!(p2(EXAMPLE), 3621);
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+
+use crate::grid::Grid;
+
+type Algorithm = Vec<bool>;
+
+#[derive(Debug, Clone)]
+struct Image {
+    pixels: Grid<bool>,
+    // Colour of the infinite area around the pixels
+    background: bool,
+}
+
+fn parse(input: &str) -> (Algorithm, Image) {
+    let (algorithm, image) = input.split_once("\n\n").unwrap();
+
+    let algorithm: Algorithm = algorithm.trim().chars().map(|c| c == '#').collect();
+    assert_eq!(algorithm.len(), 512, "The algorithm must have 512 entries");
+
+    (
+        algorithm,
+        Image {
+            pixels: Grid::parse(image.trim(), |c| c == '#'),
+            background: false,
+        },
+    )
+}
+
+fn solve(input: &str, steps: usize) -> usize {
+    let (algorithm, image) = parse(input);
+
+    (0..steps)
+        .fold(image, |image, _| image.enhance(&algorithm))
+        .lit()
+}
+
+pub fn p1(input: &str) -> usize {
+    solve(input, 2)
+}
+
+pub fn p2(input: &str) -> usize {
+    solve(input, 50)
+}
+
+impl Image {
+    fn pixel(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 {
+            return self.background;
+        }
+
+        *self
+            .pixels
+            .get((x as usize, y as usize))
+            .unwrap_or(&self.background)
+    }
+
+    // Every step the image grows by one pixel on each side
+    fn enhance(&self, algorithm: &Algorithm) -> Image {
+        let mut pixels = Grid::new(self.pixels.width() + 2, self.pixels.height() + 2, false);
+
+        for (x, y) in pixels.coordinates() {
+            let (ox, oy) = (x as isize - 1, y as isize - 1);
+            let index = (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                .fold(0, |acc, (dx, dy)| {
+                    acc << 1 | self.pixel(ox + dx, oy + dy) as usize
+                });
+
+            pixels[(x, y)] = algorithm[index];
+        }
+
+        Image {
+            pixels,
+            background: algorithm[if self.background { 511 } else { 0 }],
+        }
+    }
+
+    fn lit(&self) -> usize {
+        assert!(!self.background, "Infinitely many pixels are lit");
+
+        self.pixels.iter().filter(|(_, &lit)| lit).count()
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.pixels.map(|&lit| if lit { '#' } else { '.' }))
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+#[test]
+fn test_enhance() {
+    let (algorithm, image) = parse(EXAMPLE);
+
+    let once = image.enhance(&algorithm);
+    assert_eq!(
+        once.to_string(),
+        ".##.##.
+#..#.#.
+##.#..#
+####..#
+.#..##.
+..##..#
+...#.#.
+"
+    );
+    assert_eq!(once.enhance(&algorithm).lit(), 35);
+}
+
+#[test]
+fn test_flashing_background() {
+    // Index 0 lights up every dark pixel and index 511 turns them off again
+    let mut algorithm = vec![false; 512];
+    algorithm[0] = true;
+    let input = format!(
+        "{}\n\n...\n.#.\n...",
+        algorithm
+            .iter()
+            .map(|&b| if b { '#' } else { '.' })
+            .collect::<String>()
+    );
+
+    let (algorithm, image) = parse(&input);
+    let once = image.enhance(&algorithm);
+    assert!(once.background);
+
+    // Only the centre sees a fully dark window, the one around the original lit pixel
+    let twice = once.enhance(&algorithm);
+    assert!(!twice.background);
+    assert_eq!(twice.lit(), 1);
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(p1(EXAMPLE), 35);
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(p2(EXAMPLE), 3351);
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+const ROLLS_PER_TURN: u32 = 3;
+
+#[derive(Debug, Clone, Copy)]
+struct Game {
+    board: u32,
+    target: u32,
+    faces: u32,
+}
+
+// Position and score of the player about to play, then of the other one
+type State = ((u32, u32), (u32, u32));
+
+fn parse(input: &str) -> [u32; 2] {
+    let mut positions = input
+        .lines()
+        .map(|l| l.rsplit_once(": ").unwrap().1.trim().parse().unwrap());
+
+    [positions.next().unwrap(), positions.next().unwrap()]
+}
+
+pub fn p1(input: &str) -> u64 {
+    let game = Game {
+        board: 10,
+        target: 1000,
+        faces: 100,
+    };
+
+    game.deterministic(parse(input))
+}
+
+pub fn p2(input: &str) -> u64 {
+    let game = Game {
+        board: 10,
+        target: 21,
+        faces: 3,
+    };
+
+    let (first, second) = game.quantum(parse(input));
+    first.max(second)
+}
+
+impl Game {
+    // Positions go from 1 to `board`
+    fn advance(&self, position: u32, steps: u32) -> u32 {
+        (position - 1 + steps) % self.board + 1
+    }
+
+    // Returns the losing score multiplied by the number of rolls
+    fn deterministic(&self, start: [u32; 2]) -> u64 {
+        let mut positions = start;
+        let mut scores = [0, 0];
+        let mut rolls = 0;
+        let mut die = (1..=self.faces).cycle();
+
+        loop {
+            for player in 0..2 {
+                let steps = die.by_ref().take(ROLLS_PER_TURN as usize).sum();
+                rolls += ROLLS_PER_TURN as u64;
+
+                positions[player] = self.advance(positions[player], steps);
+                scores[player] += positions[player];
+
+                if scores[player] >= self.target {
+                    return scores[1 - player] as u64 * rolls;
+                }
+            }
+        }
+    }
+
+    // How many universes sum up to each total of a turn
+    fn outcomes(&self) -> HashMap<u32, u64> {
+        (0..ROLLS_PER_TURN).fold(HashMap::from([(0, 1)]), |acc, _| {
+            let mut next = HashMap::new();
+            for (sum, count) in acc {
+                for face in 1..=self.faces {
+                    *next.entry(sum + face).or_default() += count;
+                }
+            }
+            next
+        })
+    }
+
+    // Returns in how many universes each player wins
+    fn quantum(&self, start: [u32; 2]) -> (u64, u64) {
+        fn wins(
+            game: &Game,
+            outcomes: &HashMap<u32, u64>,
+            state: State,
+            cache: &mut HashMap<State, (u64, u64)>,
+        ) -> (u64, u64) {
+            if let Some(&known) = cache.get(&state) {
+                return known;
+            }
+
+            let ((position, score), other) = state;
+            let mut total = (0, 0);
+
+            for (&steps, &universes) in outcomes {
+                let position = game.advance(position, steps);
+                let score = score + position;
+
+                if score >= game.target {
+                    total.0 += universes;
+                } else {
+                    // The other player is now the one about to play
+                    let (other_wins, wins) =
+                        wins(game, outcomes, (other, (position, score)), cache);
+                    total.0 += universes * wins;
+                    total.1 += universes * other_wins;
+                }
+            }
+
+            cache.insert(state, total);
+            total
+        }
+
+        wins(
+            self,
+            &self.outcomes(),
+            ((start[0], 0), (start[1], 0)),
+            &mut HashMap::new(),
+        )
+    }
+}
+
+#[test]
+fn test_outcomes() {
+    let game = Game {
+        board: 10,
+        target: 21,
+        faces: 3,
+    };
+    let outcomes = game.outcomes();
+
+    assert_eq!(outcomes.values().sum::<u64>(), 27);
+    assert_eq!(outcomes[&3], 1);
+    assert_eq!(outcomes[&6], 7);
+}
+
+#[test]
+fn test_quantum() {
+    let game = Game {
+        board: 10,
+        target: 21,
+        faces: 3,
+    };
+
+    assert_eq!(game.quantum([4, 8]), (444356092776315, 341960390180808));
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(
+        p1("Player 1 starting position: 4
+Player 2 starting position: 8"),
+        739785
+    );
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(
+        p2("Player 1 starting position: 4
+Player 2 starting position: 8"),
+        444356092776315
+    );
+}
@@ -0,0 +1,260 @@
+use std::fmt;
+
+use crate::search::astar;
+
+const HALLWAY_LENGTH: usize = 11;
+const ROOMS: usize = 4;
+const MAX_DEPTH: usize = 4;
+// Hallway positions right above each room, amphipods can't stop there
+const ENTRANCES: [usize; ROOMS] = [2, 4, 6, 8];
+const ENERGY: [usize; ROOMS] = [1, 10, 100, 1000];
+
+// The lines unfolded from the diagram in part two
+const UNFOLDED: [&str; 2] = ["  #D#C#B#A#", "  #D#B#A#C#"];
+
+// Amphipods are numbered from 0 (Amber) to 3 (Desert), which is also the index of their room
+type Amphipod = u8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY_LENGTH],
+    // From the top of the room to its bottom, only the first `depth` slots are used
+    rooms: [[Option<Amphipod>; MAX_DEPTH]; ROOMS],
+    depth: usize,
+}
+
+fn parse(input: &str) -> Burrow {
+    let mut rooms = [[None; MAX_DEPTH]; ROOMS];
+    let mut depth = 0;
+
+    for l in input.lines() {
+        let amphipods: Vec<Amphipod> = l
+            .chars()
+            .filter(|c| ('A'..='D').contains(c))
+            .map(|c| c as u8 - b'A')
+            .collect();
+
+        if amphipods.len() == ROOMS {
+            for (room, &amphipod) in amphipods.iter().enumerate() {
+                rooms[room][depth] = Some(amphipod);
+            }
+            depth += 1;
+        }
+    }
+    assert!((1..=MAX_DEPTH).contains(&depth), "Invalid room depth");
+
+    Burrow {
+        hallway: [None; HALLWAY_LENGTH],
+        rooms,
+        depth,
+    }
+}
+
+fn organize(burrow: Burrow) -> usize {
+    astar(
+        burrow,
+        Burrow::is_organized,
+        Burrow::moves,
+        Burrow::min_energy_left,
+    )
+    .expect("The amphipods can't be organized")
+    .0
+}
+
+pub fn p1(input: &str) -> usize {
+    organize(parse(input))
+}
+
+pub fn p2(input: &str) -> usize {
+    let mut lines: Vec<&str> = input.lines().collect();
+    // The first line of amphipods comes right after the top wall and the hallway
+    lines.splice(3..3, UNFOLDED);
+
+    organize(parse(&lines.join("\n")))
+}
+
+impl Burrow {
+    fn is_organized(&self) -> bool {
+        (0..ROOMS).all(|room| self.room_is_final(room) && self.rooms[room][0].is_some())
+    }
+
+    // Only amphipods of the right type are in the room, so none of them need to leave
+    fn room_is_final(&self, room: usize) -> bool {
+        self.rooms[room][..self.depth]
+            .iter()
+            .flatten()
+            .all(|&amphipod| amphipod as usize == room)
+    }
+
+    // Whether the hallway is free between the two positions, `from` excluded.
+    // Entrances are never occupied, so leaving a room only needs the hallway part
+    fn hallway_is_free(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    fn moves(&self) -> Vec<(Burrow, usize)> {
+        let mut moves = Vec::new();
+
+        // Amphipods in the hallway can only go to their own room, as deep as possible
+        for (position, amphipod) in self.hallway.iter().enumerate() {
+            let Some(amphipod) = *amphipod else {
+                continue;
+            };
+            let room = amphipod as usize;
+            let entrance = ENTRANCES[room];
+
+            if !self.room_is_final(room) || !self.hallway_is_free(position, entrance) {
+                continue;
+            }
+
+            let slot = self.rooms[room][..self.depth]
+                .iter()
+                .rposition(Option::is_none)
+                .unwrap();
+
+            let mut next = *self;
+            next.hallway[position] = None;
+            next.rooms[room][slot] = Some(amphipod);
+            let steps = position.abs_diff(entrance) + slot + 1;
+            moves.push((next, steps * ENERGY[room]));
+        }
+
+        // The top amphipod of a room that is not final can go to any free spot of the hallway
+        for room in (0..ROOMS).filter(|&room| !self.room_is_final(room)) {
+            let slot = self.rooms[room].iter().position(Option::is_some).unwrap();
+            let amphipod = self.rooms[room][slot].unwrap();
+            let entrance = ENTRANCES[room];
+
+            for position in 0..HALLWAY_LENGTH {
+                if ENTRANCES.contains(&position) || !self.hallway_is_free(entrance, position) {
+                    continue;
+                }
+                let mut next = *self;
+                next.rooms[room][slot] = None;
+                next.hallway[position] = Some(amphipod);
+                let steps = slot + 1 + entrance.abs_diff(position);
+                moves.push((next, steps * ENERGY[amphipod as usize]));
+            }
+        }
+
+        moves
+    }
+
+    // Every amphipod out of place has at least to walk to the top of its room
+    fn min_energy_left(&self) -> usize {
+        let in_hallway: usize = self
+            .hallway
+            .iter()
+            .enumerate()
+            .filter_map(|(position, amphipod)| {
+                let room = (*amphipod)? as usize;
+                Some((position.abs_diff(ENTRANCES[room]) + 1) * ENERGY[room])
+            })
+            .sum();
+
+        let in_rooms: usize = (0..ROOMS)
+            .map(|room| {
+                // The amphipods below the last misplaced one can stay where they are
+                let leaving = self.rooms[room][..self.depth]
+                    .iter()
+                    .rposition(|&a| a.is_some_and(|a| a as usize != room))
+                    .map_or(0, |last| last + 1);
+
+                self.rooms[room][..leaving]
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(slot, amphipod)| {
+                        let target = (*amphipod)? as usize;
+                        // Blocking amphipods have to step aside and come back
+                        let across = ENTRANCES[room].abs_diff(ENTRANCES[target]).max(2);
+                        Some((slot + 1 + across + 1) * ENERGY[target])
+                    })
+                    .sum::<usize>()
+            })
+            .sum();
+
+        in_hallway + in_rooms
+    }
+}
+
+impl fmt::Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letter = |amphipod: Option<Amphipod>| amphipod.map_or('.', |a| (b'A' + a) as char);
+
+        writeln!(f, "{}", "#".repeat(HALLWAY_LENGTH + 2))?;
+        writeln!(
+            f,
+            "#{}#",
+            self.hallway.iter().map(|&a| letter(a)).collect::<String>()
+        )?;
+
+        for slot in 0..self.depth {
+            let (left, right) = if slot == 0 {
+                ("###", "###")
+            } else {
+                ("  #", "#")
+            };
+            let amphipods: Vec<String> = self
+                .rooms
+                .iter()
+                .map(|room| letter(room[slot]).to_string())
+                .collect();
+            writeln!(f, "{}{}{}", left, amphipods.join("#"), right)?;
+        }
+
+        write!(f, "  {}", "#".repeat(HALLWAY_LENGTH - 2))
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+#[test]
+fn test_parse() {
+    let burrow = parse(EXAMPLE);
+
+    assert_eq!(burrow.depth, 2);
+    assert_eq!(burrow.to_string(), EXAMPLE);
+    assert!(!burrow.is_organized());
+    assert!(parse(
+        "#############
+#...........#
+###A#B#C#D###
+  #A#B#C#D#
+  #########"
+    )
+    .is_organized());
+}
+
+#[test]
+fn test_moves() {
+    let burrow = parse(EXAMPLE);
+
+    // Each of the 4 top amphipods can go to 7 spots of the hallway
+    let moves = burrow.moves();
+    assert_eq!(moves.len(), 28);
+    // The estimate never drops by more than the energy actually spent
+    assert!(moves
+        .iter()
+        .all(|(next, energy)| energy + next.min_energy_left() >= burrow.min_energy_left()));
+}
+
+#[test]
+fn test_p1() {
+    assert_eq!(p1(EXAMPLE), 12521);
+}
+
+#[test]
+fn test_p2() {
+    assert_eq!(p2(EXAMPLE), 44169);
+}
@@ -0,0 +1,351 @@
+use std::str::FromStr;
+
+// MONAD checks each digit with the same 18 instructions, only three parameters change
+const BLOCK_LENGTH: usize = 18;
+
+// Registers w, x, y and z are stored in this order
+type Register = usize;
+const W: Register = 0;
+const X: Register = 1;
+const Y: Register = 2;
+const Z: Register = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Register(Register),
+    Value(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+#[derive(Debug, Default)]
+struct Alu {
+    registers: [i64; 4],
+}
+
+// The parameters of a block: the divisor of z and the values added to x and y
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    div_z: i64,
+    add_x: i64,
+    add_y: i64,
+}
+
+fn parse(input: &str) -> Vec<Instruction> {
+    input.lines().map(|l| l.parse().unwrap()).collect()
+}
+
+pub fn p1(input: &str) -> u64 {
+    let program = parse(input);
+    let (_, largest) = model_numbers(&program);
+
+    assert!(is_valid(&program, largest), "MONAD rejects {}", largest);
+    largest
+}
+
+pub fn p2(input: &str) -> u64 {
+    let program = parse(input);
+    let (smallest, _) = model_numbers(&program);
+
+    assert!(is_valid(&program, smallest), "MONAD rejects {}", smallest);
+    smallest
+}
+
+fn parse_register(s: &str) -> Result<Register, ()> {
+    match s {
+        "w" => Ok(W),
+        "x" => Ok(X),
+        "y" => Ok(Y),
+        "z" => Ok(Z),
+        _ => Err(()),
+    }
+}
+
+impl FromStr for Operand {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_register(s)
+            .map(Operand::Register)
+            .or_else(|_| s.parse().map(Operand::Value).map_err(|_| ()))
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let op = parts.next().ok_or(())?;
+        let a = parse_register(parts.next().ok_or(())?)?;
+
+        if op == "inp" {
+            return match parts.next() {
+                None => Ok(Instruction::Inp(a)),
+                Some(_) => Err(()),
+            };
+        }
+
+        let b = parts.next().ok_or(())?.parse()?;
+        if parts.next().is_some() {
+            return Err(());
+        }
+
+        match op {
+            "add" => Ok(Instruction::Add(a, b)),
+            "mul" => Ok(Instruction::Mul(a, b)),
+            "div" => Ok(Instruction::Div(a, b)),
+            "mod" => Ok(Instruction::Mod(a, b)),
+            "eql" => Ok(Instruction::Eql(a, b)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Alu {
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.registers[r],
+            Operand::Value(v) => v,
+        }
+    }
+
+    // Fails when the input runs out, on a division by zero or on a modulo of negative numbers
+    fn run(&mut self, program: &[Instruction], input: &[i64]) -> Result<(), ()> {
+        let mut input = input.iter();
+
+        for &instruction in program {
+            match instruction {
+                Instruction::Inp(a) => self.registers[a] = *input.next().ok_or(())?,
+                Instruction::Add(a, b) => self.registers[a] += self.value(b),
+                Instruction::Mul(a, b) => self.registers[a] *= self.value(b),
+                Instruction::Div(a, b) => {
+                    let b = self.value(b);
+                    if b == 0 {
+                        return Err(());
+                    }
+                    self.registers[a] /= b;
+                }
+                Instruction::Mod(a, b) => {
+                    let b = self.value(b);
+                    if self.registers[a] < 0 || b <= 0 {
+                        return Err(());
+                    }
+                    self.registers[a] %= b;
+                }
+                Instruction::Eql(a, b) => {
+                    self.registers[a] = (self.registers[a] == self.value(b)) as i64
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Runs MONAD on a candidate on its own, without any of the analysis
+pub fn check(input: &str, model: u64) -> bool {
+    is_valid(&parse(input), model)
+}
+
+// A candidate has one non zero digit per input read, and MONAD must leave 0 in z
+fn is_valid(program: &[Instruction], model: u64) -> bool {
+    let digits: Vec<i64> = model
+        .to_string()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as i64)
+        .collect();
+    let inputs = program
+        .iter()
+        .filter(|i| matches!(i, Instruction::Inp(_)))
+        .count();
+
+    if digits.len() != inputs || digits.contains(&0) {
+        return false;
+    }
+
+    let mut alu = Alu::default();
+    alu.run(program, &digits).is_ok() && alu.registers[Z] == 0
+}
+
+impl Block {
+    fn new(instructions: &[Instruction]) -> Self {
+        assert_eq!(instructions.len(), BLOCK_LENGTH, "Truncated MONAD block");
+
+        match (
+            instructions[0],
+            instructions[4],
+            instructions[5],
+            instructions[15],
+        ) {
+            (
+                Instruction::Inp(W),
+                Instruction::Div(Z, Operand::Value(div_z)),
+                Instruction::Add(X, Operand::Value(add_x)),
+                Instruction::Add(Y, Operand::Value(add_y)),
+            ) => Self {
+                div_z,
+                add_x,
+                add_y,
+            },
+            _ => panic!("Unexpected MONAD block: {:?}", instructions),
+        }
+    }
+}
+
+// z works as a base 26 stack: blocks dividing z by 1 push their digit plus add_y, the others
+// pop it and only avoid pushing again when their digit is the popped one plus add_x.
+// Each pair of blocks then ties two digits: digit[pop] = digit[push] + add_y[push] + add_x[pop]
+fn model_numbers(program: &[Instruction]) -> (u64, u64) {
+    let blocks: Vec<Block> = program.chunks(BLOCK_LENGTH).map(Block::new).collect();
+
+    let mut smallest = vec![0; blocks.len()];
+    let mut largest = vec![0; blocks.len()];
+    let mut stack = Vec::new();
+
+    for (i, block) in blocks.iter().enumerate() {
+        if block.div_z == 1 {
+            stack.push((i, block.add_y));
+            continue;
+        }
+
+        let (j, add_y) = stack.pop().expect("More pops than pushes in MONAD");
+        let diff = add_y + block.add_x;
+        assert!(diff.abs() < 9, "Digits {} and {} can't be matched", j, i);
+
+        largest[j] = 9 - diff.max(0);
+        largest[i] = largest[j] + diff;
+        smallest[j] = 1 - diff.min(0);
+        smallest[i] = smallest[j] + diff;
+    }
+    assert!(stack.is_empty(), "More pushes than pops in MONAD");
+
+    let number = |digits: Vec<i64>| digits.iter().fold(0, |acc, &d| acc * 10 + d as u64);
+    (number(smallest), number(largest))
+}
+
+#[cfg(test)]
+fn monad(blocks: &[(i64, i64, i64)]) -> String {
+    blocks
+        .iter()
+        .map(|(div_z, add_x, add_y)| {
+            format!(
+                "inp w
+mul x 0
+add x z
+mod x 26
+div z {}
+add x {}
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y {}
+mul y x
+add z y",
+                div_z, add_x, add_y
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+const BLOCKS: [(i64, i64, i64); 14] = [
+    (1, 12, 4),
+    (1, 15, 11),
+    (1, 11, 7),
+    (26, -14, 2),
+    (1, 12, 11),
+    (26, -10, 13),
+    (1, 11, 9),
+    (1, 13, 12),
+    (26, -7, 6),
+    (1, 10, 2),
+    (26, -2, 11),
+    (26, -1, 12),
+    (26, -4, 3),
+    (26, -12, 13),
+];
+
+#[test]
+fn test_alu() {
+    let mut alu = Alu::default();
+    alu.run(&parse("inp x\nmul x -1"), &[7]).unwrap();
+    assert_eq!(alu.registers[X], -7);
+
+    let three_times = parse("inp z\ninp x\nmul z 3\neql z x");
+    let mut alu = Alu::default();
+    alu.run(&three_times, &[3, 9]).unwrap();
+    assert_eq!(alu.registers[Z], 1);
+
+    let binary = parse(
+        "inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2",
+    );
+    let mut alu = Alu::default();
+    alu.run(&binary, &[13]).unwrap();
+    assert_eq!(alu.registers, [1, 1, 0, 1]);
+
+    assert_eq!(Alu::default().run(&three_times, &[3]), Err(()));
+    assert_eq!(Alu::default().run(&parse("div x 0"), &[]), Err(()));
+    assert_eq!("sub x 1".parse::<Instruction>(), Err(()));
+    assert_eq!("inp x 1".parse::<Instruction>(), Err(()));
+}
+
+#[test]
+fn test_model_numbers() {
+    // Small enough to check every candidate with the interpreter
+    let blocks = [(1, 14, 3), (1, 11, 8), (26, -9, 5), (26, -6, 1)];
+    let input = monad(&blocks);
+
+    let valid: Vec<u64> = (1111..=9999).filter(|&n| check(&input, n)).collect();
+
+    assert_eq!(
+        model_numbers(&parse(&input)),
+        (valid[0], *valid.last().unwrap())
+    );
+    // One digit per input
+    assert!(!check(&input, valid[0] * 10 + 1));
+    assert!(!check(&input, valid[0] / 10));
+}
+
+#[test]
+fn test_p1() {
+    let input = monad(&BLOCKS);
+    let largest = p1(&input);
+
+    assert!(is_valid(&parse(&input), largest));
+    assert!(!is_valid(&parse(&input), largest + 1));
+}
+
+#[test]
+fn test_p2() {
+    let input = monad(&BLOCKS);
+    let smallest = p2(&input);
+
+    assert!(is_valid(&parse(&input), smallest));
+    assert!(!is_valid(&parse(&input), smallest - 1));
+}
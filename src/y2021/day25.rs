@@ -0,0 +1,169 @@
+use std::fmt;
+
+use crate::grid::{Coordinate, Grid};
+use crate::repl::Simulation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+#[derive(Debug)]
+pub struct SeaFloor {
+    // The herds leaving one edge come back from the opposite one
+    cucumbers: Grid<Cell>,
+    step: usize,
+    settled: bool,
+}
+
+fn parse(input: &str) -> Grid<Cell> {
+    Grid::parse(input.trim(), |c| match c {
+        '>' => Cell::East,
+        'v' => Cell::South,
+        '.' => Cell::Empty,
+        _ => panic!("Unknown cell {}", c),
+    })
+}
+
+// Every cucumber of the herd looks ahead before any of them moves
+fn move_herd(cucumbers: &mut Grid<Cell>, herd: Cell, delta: (isize, isize)) -> bool {
+    let moving: Vec<(Coordinate, Coordinate)> = cucumbers
+        .iter()
+        .filter(|(_, &cell)| cell == herd)
+        .map(|(coord, _)| (coord, cucumbers.wrapping_offset(coord, delta)))
+        .filter(|&(_, ahead)| cucumbers[ahead] == Cell::Empty)
+        .collect();
+
+    for &(from, to) in &moving {
+        cucumbers[from] = Cell::Empty;
+        cucumbers[to] = herd;
+    }
+
+    !moving.is_empty()
+}
+
+pub fn p1(input: &str) -> usize {
+    let mut sea_floor = SeaFloor::new(input);
+    while sea_floor.step() {}
+
+    sea_floor.steps()
+}
+
+// There is no second part on the last day
+pub fn p2(_input: &str) {}
+
+impl SeaFloor {
+    fn new(input: &str) -> Self {
+        SeaFloor {
+            cucumbers: parse(input),
+            step: 0,
+            settled: false,
+        }
+    }
+}
+
+impl Simulation for SeaFloor {
+    fn load(input: &str) -> Result<Self, String> {
+        Ok(SeaFloor::new(input))
+    }
+
+    // The step where nothing moves still counts, the ones after it don't
+    fn step(&mut self) -> bool {
+        if self.settled {
+            return false;
+        }
+
+        let east = move_herd(&mut self.cucumbers, Cell::East, (1, 0));
+        let south = move_herd(&mut self.cucumbers, Cell::South, (0, 1));
+        self.settled = !east && !south;
+        self.step += 1;
+
+        !self.settled
+    }
+
+    fn steps(&self) -> usize {
+        self.step
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+impl fmt::Display for SeaFloor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.settled {
+            writeln!(f, "Nothing moved during step {}:", self.step)?;
+        } else {
+            writeln!(f, "After step {}:", self.step)?;
+        }
+        write!(f, "{}", self.cucumbers)
+    }
+}
+
+#[cfg(test)]
+fn after(input: &str, steps: usize) -> String {
+    let mut sea_floor = SeaFloor::new(input);
+    (0..steps).for_each(|_| {
+        sea_floor.step();
+    });
+
+    sea_floor.cucumbers.to_string()
+}
+
+#[test]
+fn test_wrapping() {
+    assert_eq!(after("...>>>>>...", 1), "...>>>>.>..\n");
+    assert_eq!(after("...>>>>>...", 2), "...>>>.>.>.\n");
+
+    assert_eq!(
+        after(
+            "...>...
+.......
+......>
+v.....>
+......>
+.......
+..vvv..",
+            1
+        ),
+        "..vv>..
+.......
+>......
+v.....>
+>......
+.......
+....v..
+"
+    );
+}
+
+#[test]
+fn test_p1() {
+    let input = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    assert_eq!(p1(input), 58);
+    assert_eq!(after(input, 58), after(input, 57));
+
+    let mut sea_floor = SeaFloor::new(input);
+    while sea_floor.step() {}
+    assert!(!sea_floor.step());
+    assert_eq!(sea_floor.steps(), 58);
+}
@@ -0,0 +1,130 @@
+use crate::registry::{solution, streamed, Data, Exporter, Part, Repl, Stream, Visualizer};
+use crate::repl;
+
+pub mod day01;
+pub mod day01_bis;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+const DEFAULT_OCTOPUS_STEPS: usize = 100;
+
+pub fn parts(day: usize) -> Option<(Part, Part)> {
+    let parts: (Part, Part) = match day {
+        1 => solution!(day01),
+        2 => solution!(day02),
+        3 => solution!(day03),
+        4 => solution!(day04),
+        5 => solution!(day05),
+        6 => solution!(day06),
+        7 => solution!(day07),
+        8 => solution!(day08),
+        9 => solution!(day09),
+        10 => solution!(day10),
+        11 => solution!(day11),
+        12 => solution!(day12),
+        13 => solution!(day13),
+        14 => solution!(day14),
+        15 => solution!(day15),
+        16 => solution!(day16),
+        17 => solution!(day17),
+        18 => solution!(day18),
+        19 => solution!(day19),
+        20 => solution!(day20),
+        21 => solution!(day21),
+        22 => solution!(day22),
+        23 => solution!(day23),
+        24 => solution!(day24),
+        25 => solution!(day25),
+        _ => return None,
+    };
+
+    Some(parts)
+}
+
+pub fn stream(day: usize) -> Option<(Stream, Stream)> {
+    let stream: (Stream, Stream) = match day {
+        1 => streamed!(day01),
+        2 => streamed!(day02),
+        10 => streamed!(day10),
+        _ => return None,
+    };
+
+    Some(stream)
+}
+
+pub fn repl(day: usize) -> Option<Repl> {
+    let repl: Repl = match day {
+        4 => repl::run::<day04::Bingo>,
+        6 => repl::run::<day06::School>,
+        11 => repl::run::<day11::Cavern>,
+        13 => repl::run::<day13::Paper>,
+        14 => repl::run::<day14::Polymer>,
+        25 => repl::run::<day25::SeaFloor>,
+        _ => return None,
+    };
+
+    Some(repl)
+}
+
+pub fn visualizer(day: usize) -> Option<Visualizer> {
+    let visualizer: Visualizer = match day {
+        11 => day11::visualize,
+        13 => day13::visualize,
+        15 => day15::visualize,
+        17 => day17::visualize,
+        _ => return None,
+    };
+
+    Some(visualizer)
+}
+
+// Day 11 takes the number of steps to simulate and day 15 the number of tiles of the map
+pub fn exporter(day: usize) -> Option<Exporter> {
+    let exporter: Exporter = match day {
+        5 => |input, path, _| day05::image(input).save(path),
+        9 => |input, path, _| day09::image(input).save(path),
+        11 => |input, path, steps| {
+            day11::image(input, steps.unwrap_or(DEFAULT_OCTOPUS_STEPS)).save(path)
+        },
+        13 => |input, path, _| day13::image(input).save(path),
+        15 => |input, path, tiles| day15::image(input, tiles.unwrap_or(1)).save(path),
+        _ => return None,
+    };
+
+    Some(exporter)
+}
+
+// Day 2 exports the trajectory of the submarine under the model of each part
+pub fn data(day: usize) -> Option<(Data, Data)> {
+    let data: (Data, Data) = match day {
+        2 => (
+            |input| day02::trajectory_csv(input, day02::make_move).map_err(|err| err.to_string()),
+            |input| day02::trajectory_csv(input, day02::make_move2).map_err(|err| err.to_string()),
+        ),
+        _ => return None,
+    };
+
+    Some(data)
+}